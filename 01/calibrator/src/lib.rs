@@ -0,0 +1,611 @@
+#![allow(clippy::needless_return)]
+
+extern crate aoc_common;
+extern crate num_bigint;
+
+use aoc_common::{Answer, Error, Solution};
+use num_bigint::BigInt;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::Hash;
+use std::io;
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(calibrate(input)?.into())
+    }
+}
+
+/// A number a frequency can be added up in. Converting to and from `BigInt`
+/// lets frequencies be worked with without worrying about overflow, and
+/// shows whether a result fits back in the original width.
+pub trait Frequency: Clone + Eq + Ord + Hash + fmt::Display + FromStr + Into<BigInt> + TryFrom<BigInt> {
+    /// What the number is called in errors.
+    const DESCRIPTION: &'static str;
+
+    fn zero() -> Self;
+
+    /// Applies a change to the frequency, or `None` if the result doesn't
+    /// fit.
+    fn apply(&self, change: &Self) -> Option<Self>;
+}
+
+impl Frequency for i64 {
+    const DESCRIPTION: &'static str = "a 64 bit integer";
+
+    fn zero() -> i64 {
+        0
+    }
+
+    fn apply(&self, change: &i64) -> Option<i64> {
+        self.checked_add(*change)
+    }
+}
+
+impl Frequency for i128 {
+    const DESCRIPTION: &'static str = "a 128 bit integer";
+
+    fn zero() -> i128 {
+        0
+    }
+
+    fn apply(&self, change: &i128) -> Option<i128> {
+        self.checked_add(*change)
+    }
+}
+
+impl Frequency for BigInt {
+    const DESCRIPTION: &'static str = "an arbitrary precision integer";
+
+    fn zero() -> BigInt {
+        BigInt::from(0)
+    }
+
+    fn apply(&self, change: &BigInt) -> Option<BigInt> {
+        Some(self + change)
+    }
+}
+
+/// Which kind of number to add frequencies up in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Width {
+    I64,
+    I128,
+    Big,
+}
+
+impl FromStr for Width {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Width, String> {
+        match string {
+            "i64" => Ok(Width::I64),
+            "i128" => Ok(Width::I128),
+            "big" => Ok(Width::Big),
+            other => Err(format!("Unknown width '{}', expected i64, i128 or big", other)),
+        }
+    }
+}
+
+/// Which table of the frequency's walk to print in place of the answer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Report {
+    /// A row for every pass over the changes.
+    Passes,
+    /// A row for every frequency reached again.
+    Repeats,
+}
+
+impl FromStr for Report {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Report, String> {
+        match string {
+            "passes" => Ok(Report::Passes),
+            "repeats" => Ok(Report::Repeats),
+            other => Err(format!("Unknown report '{}', expected passes or repeats", other)),
+        }
+    }
+}
+
+/// The command line of a day 1 binary.
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub width: Width,
+    pub report: Option<Report>,
+    /// How many passes over the changes a report covers.
+    pub passes: Option<usize>,
+    /// Whether to print the running total as the input is read.
+    pub stream: bool,
+    pub input: Option<String>,
+}
+
+/// Reads the command line of a day 1 binary: an optional `--width`, an
+/// optional `--report` with how many `--passes` it covers or `--stream`,
+/// and the input to read.
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { width: Width::I64, report: None, passes: None, stream: false, input: None };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" => options.width = option_value(arg, args.next())?.parse()?,
+            "--report" => options.report = Some(option_value(arg, args.next())?.parse()?),
+            "--passes" => {
+                let value = option_value(arg, args.next())?;
+                let passes = value.parse()
+                    .map_err(|_| format!("Expected a number for --passes, got '{}'", value))?;
+                options.passes = Some(passes);
+            },
+            "--stream" => options.stream = true,
+            _ if options.input.is_none() => options.input = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    if options.passes.is_some() && options.report.is_none() {
+        return Err(String::from("--passes is only for a --report"));
+    }
+    if options.stream && options.report.is_some() {
+        return Err(String::from("--stream can't be used with a --report"));
+    }
+
+    Ok(options)
+}
+
+fn option_value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", option))
+}
+
+/// A frequency change and the line of the input it's on.
+#[derive(Debug, PartialEq)]
+pub struct Change<T> {
+    pub line: usize,
+    pub amount: T,
+}
+
+impl<T: Frequency> Change<T> {
+    /// Applies the change to a frequency, reporting the line it's on if the
+    /// result doesn't fit.
+    pub fn apply_to(&self, frequency: &T) -> Result<T, Error> {
+        frequency.apply(&self.amount).ok_or_else(|| overflow_error::<T>(self.line))
+    }
+}
+
+/// The error for a frequency growing too big for `T` at a line.
+pub fn overflow_error<T: Frequency>(line: usize) -> Error {
+    Error::Invalid(format!("the frequency no longer fits in {} after line {}", T::DESCRIPTION, line))
+}
+
+/// Parses one frequency change per line of `input`.
+pub fn read_changes<T: Frequency>(input: &str) -> Result<Vec<Change<T>>, Error> {
+    let expected = format!("a frequency change that fits in {}", T::DESCRIPTION);
+    let amounts = aoc_common::parse_lines_with(input, |line| {
+        aoc_common::parse_field(line, line, &expected)
+    })?;
+
+    // Blank lines are skipped, so line the changes back up with the lines
+    // they came from
+    let lines = input.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, _)| index + 1);
+
+    Ok(lines.zip(amounts).map(|(line, amount)| Change { line, amount }).collect())
+}
+
+/// Adds up the frequency changes, checking each step fits.
+pub fn calibrate_as<T: Frequency>(input: &str) -> Result<T, Error> {
+    let mut total_calibration = T::zero();
+    for change in read_changes(input)? {
+        total_calibration = change.apply_to(&total_calibration)?;
+    }
+
+    return Ok(total_calibration);
+}
+
+pub fn calibrate(input: &str) -> Result<i64, Error> {
+    calibrate_as(input)
+}
+
+/// Adds up the frequency changes in the chosen width.
+pub fn calibrate_with_width(input: &str, width: Width) -> Result<String, Error> {
+    match width {
+        Width::I64 => Ok(calibrate_as::<i64>(input)?.to_string()),
+        Width::I128 => Ok(calibrate_as::<i128>(input)?.to_string()),
+        Width::Big => Ok(calibrate_as::<BigInt>(input)?.to_string()),
+    }
+}
+
+/// The frequency after a change read from a stream.
+#[derive(Debug, PartialEq)]
+pub struct Step<T> {
+    /// The line of the change.
+    pub line: usize,
+    pub frequency: T,
+    /// Whether this is the first time any frequency has been reached again.
+    pub first_repeat: bool,
+}
+
+impl<T: Frequency> fmt::Display for Step<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.first_repeat {
+            write!(f, "{} (first repeat)", self.frequency)
+        } else {
+            write!(f, "{}", self.frequency)
+        }
+    }
+}
+
+/// Adds up frequency changes as they are read, one line at a time, giving
+/// the frequency after each. The input is never held whole, so it can go on
+/// forever. Frequencies are remembered only until one repeats, after which
+/// just the running total is kept.
+///
+/// The first bad line or overflow ends the stream with an error.
+pub struct Calibration<R, T> {
+    lines: io::Lines<R>,
+    line: usize,
+    frequency: T,
+    seen: Option<HashSet<T>>,
+    failed: bool,
+}
+
+/// Starts adding up the frequency changes read from `reader`.
+pub fn calibrate_stream<R: BufRead, T: Frequency>(reader: R) -> Calibration<R, T> {
+    let mut seen = HashSet::new();
+    seen.insert(T::zero());
+
+    Calibration {
+        lines: reader.lines(),
+        line: 0,
+        frequency: T::zero(),
+        seen: Some(seen),
+        failed: false,
+    }
+}
+
+impl<R: BufRead, T: Frequency> Calibration<R, T> {
+    fn next_step(&mut self) -> Option<Result<Step<T>, Error>> {
+        loop {
+            let text = match self.lines.next()? {
+                Ok(text) => text,
+                Err(error) => {
+                    let message = format!("failed to read line {}: {}", self.line + 1, error);
+                    return Some(Err(Error::Invalid(message)));
+                },
+            };
+            self.line += 1;
+
+            let trimmed = text.trim();
+            if !trimmed.is_empty() {
+                let indent = text.len() - text.trim_start().len();
+                return Some(self.apply(trimmed, indent));
+            }
+        }
+    }
+
+    fn apply(&mut self, text: &str, indent: usize) -> Result<Step<T>, Error> {
+        let expected = format!("a frequency change that fits in {}", T::DESCRIPTION);
+        let amount = aoc_common::parse_field(text, text, &expected)
+            .map_err(|error| error.at_line(self.line).offset_by(indent))?;
+        let change = Change { line: self.line, amount };
+        self.frequency = change.apply_to(&self.frequency)?;
+
+        let first_repeat = match self.seen.as_mut() {
+            Some(seen) => !seen.insert(self.frequency.clone()),
+            None => false,
+        };
+        if first_repeat {
+            self.seen = None;
+        }
+
+        Ok(Step { line: self.line, frequency: self.frequency.clone(), first_repeat })
+    }
+}
+
+impl<R: BufRead, T: Frequency> Iterator for Calibration<R, T> {
+    type Item = Result<Step<T>, Error>;
+
+    fn next(&mut self) -> Option<Result<Step<T>, Error>> {
+        if self.failed {
+            return None;
+        }
+
+        let step = self.next_step();
+        if let Some(Err(_)) = step {
+            self.failed = true;
+        }
+        return step;
+    }
+}
+
+/// How the frequency moved during one pass over the changes.
+#[derive(Debug, PartialEq)]
+pub struct PassSummary<T> {
+    /// Which pass this was, counting from 1.
+    pub pass: usize,
+    /// How far the frequency moved over the whole pass.
+    pub drift: T,
+    /// The lowest frequency reached during the pass.
+    pub lowest: T,
+    /// The highest frequency reached during the pass.
+    pub highest: T,
+}
+
+/// A frequency reached again.
+#[derive(Debug, PartialEq)]
+pub struct RepeatedFrequency<T> {
+    /// Which pass it was reached again in, counting from 1.
+    pub pass: usize,
+    /// The line of the change that reached it.
+    pub line: usize,
+    pub frequency: T,
+}
+
+/// The walk the frequency takes over some number of passes.
+#[derive(Debug, PartialEq)]
+pub struct Trace<T> {
+    pub passes: Vec<PassSummary<T>>,
+    /// Every frequency reached again, in the order they were reached, so
+    /// the first is the first repeat.
+    pub repeats: Vec<RepeatedFrequency<T>>,
+}
+
+impl<T: Frequency> Trace<T> {
+    /// The pass summaries as CSV, with a header row.
+    pub fn passes_csv(&self) -> String {
+        let mut csv = String::from("pass,drift,lowest,highest\n");
+        for pass in &self.passes {
+            csv += &format!("{},{},{},{}\n", pass.pass, pass.drift, pass.lowest, pass.highest);
+        }
+        return csv;
+    }
+
+    /// The repeated frequencies as CSV, with a header row.
+    pub fn repeats_csv(&self) -> String {
+        let mut csv = String::from("pass,line,frequency\n");
+        for repeat in &self.repeats {
+            csv += &format!("{},{},{}\n", repeat.pass, repeat.line, repeat.frequency);
+        }
+        return csv;
+    }
+
+    pub fn to_csv(&self, report: Report) -> String {
+        match report {
+            Report::Passes => self.passes_csv(),
+            Report::Repeats => self.repeats_csv(),
+        }
+    }
+}
+
+/// Follows the frequency through `passes` passes over the changes, noting
+/// how far it went in each and every frequency it came back to.
+pub fn trace<T: Frequency>(changes: &[Change<T>], passes: usize) -> Result<Trace<T>, Error> {
+    let mut trace = Trace { passes: Vec::new(), repeats: Vec::new() };
+    if changes.is_empty() {
+        return Ok(trace);
+    }
+
+    let mut seen = HashSet::new();
+    let mut frequency = T::zero();
+    seen.insert(frequency.clone());
+    let mut drift = None;
+    for pass in 1..=passes {
+        let mut lowest: Option<T> = None;
+        let mut highest: Option<T> = None;
+        for change in changes {
+            frequency = change.apply_to(&frequency)?;
+            if lowest.as_ref().is_none_or(|lowest| frequency < *lowest) {
+                lowest = Some(frequency.clone());
+            }
+            if highest.as_ref().is_none_or(|highest| frequency > *highest) {
+                highest = Some(frequency.clone());
+            }
+            if !seen.insert(frequency.clone()) {
+                trace.repeats.push(RepeatedFrequency { pass, line: change.line, frequency: frequency.clone() });
+            }
+        }
+
+        // Every pass moves the frequency as far as the first did
+        let drift = drift.get_or_insert_with(|| frequency.clone()).clone();
+        trace.passes.push(PassSummary { pass, drift, lowest: lowest.unwrap(), highest: highest.unwrap() });
+    }
+
+    return Ok(trace);
+}
+
+/// Traces the frequency through `passes` passes in the chosen width, as
+/// the CSV for a report.
+pub fn report_with_width(input: &str, width: Width, report: Report, passes: usize) -> Result<String, Error> {
+    match width {
+        Width::I64 => Ok(trace(&read_changes::<i64>(input)?, passes)?.to_csv(report)),
+        Width::I128 => Ok(trace(&read_changes::<i128>(input)?, passes)?.to_csv(report)),
+        Width::Big => Ok(trace(&read_changes::<BigInt>(input)?, passes)?.to_csv(report)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "+1\n-2\n+3\n+1\n";
+        let result = calibrate(input).unwrap();
+
+        assert_eq!(3, result);
+    }
+
+    #[test]
+    fn test_reports_bad_lines() {
+        let input = "+1\nplus two\n+3\n1-\n";
+        match calibrate(input) {
+            Err(Error::Parse(errors)) => {
+                let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+                assert_eq!(vec![2, 4], lines);
+            },
+            other => panic!("Expected parse errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_overflow_reports_line() {
+        let input = "+9223372036854775000\n+800\n\n+8\n-9\n";
+        let expected = Error::Invalid(String::from("the frequency no longer fits in a 64 bit integer after line 4"));
+        assert_eq!(Err(expected), calibrate(input));
+        assert_eq!(Ok(9223372036854775799), calibrate_as::<i128>(input));
+    }
+
+    #[test]
+    fn test_widths() {
+        let input = "+170141183460469231731687303715884105727\n+1\n";
+        assert!(calibrate_with_width(input, Width::I64).is_err());
+        assert!(calibrate_with_width(input, Width::I128).is_err());
+        assert_eq!(Ok(String::from("170141183460469231731687303715884105728")), calibrate_with_width(input, Width::Big));
+    }
+
+    #[test]
+    fn test_change_too_big_to_parse() {
+        match calibrate("+1\n+9223372036854775808\n") {
+            Err(Error::Parse(errors)) => {
+                assert_eq!(2, errors[0].line);
+                assert_eq!("expected a frequency change that fits in a 64 bit integer", errors[0].reason);
+            },
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|arg| arg.to_string()).collect() };
+        let options = parse_args(&args(&[])).unwrap();
+        assert_eq!(Options { width: Width::I64, report: None, passes: None, stream: false, input: None }, options);
+
+        let options = parse_args(&args(&["--width", "big", "-"])).unwrap();
+        assert_eq!((Width::Big, Some(String::from("-"))), (options.width, options.input));
+
+        let options = parse_args(&args(&["in.txt", "--report", "repeats", "--passes", "3"])).unwrap();
+        assert_eq!(Options { width: Width::I64, report: Some(Report::Repeats), passes: Some(3), stream: false, input: Some(String::from("in.txt")) }, options);
+
+        let options = parse_args(&args(&["--stream", "--width", "i128"])).unwrap();
+        assert_eq!((Width::I128, true), (options.width, options.stream));
+
+        assert!(parse_args(&args(&["--width", "i32"])).is_err());
+        assert!(parse_args(&args(&["--width"])).is_err());
+        assert!(parse_args(&args(&["--report", "drift"])).is_err());
+        assert!(parse_args(&args(&["--passes", "3"])).is_err());
+        assert!(parse_args(&args(&["a.txt", "b.txt"])).is_err());
+        assert!(parse_args(&args(&["--stream", "--report", "passes"])).is_err());
+    }
+
+    #[test]
+    fn test_trace() {
+        let changes = read_changes::<i64>("+3\n+3\n+4\n-2\n-4\n").unwrap();
+        let trace = trace(&changes, 2).unwrap();
+
+        let expected = vec![
+            PassSummary { pass: 1, drift: 4, lowest: 3, highest: 10 },
+            PassSummary { pass: 2, drift: 4, lowest: 7, highest: 14 },
+        ];
+        assert_eq!(expected, trace.passes);
+
+        let expected = vec![
+            RepeatedFrequency { pass: 2, line: 2, frequency: 10 },
+            RepeatedFrequency { pass: 2, line: 5, frequency: 8 },
+        ];
+        assert_eq!(expected, trace.repeats);
+    }
+
+    #[test]
+    fn test_trace_csv() {
+        let input = "+1\n-2\n+3\n+1\n";
+        let expected = "pass,drift,lowest,highest\n1,3,-1,3\n2,3,2,6\n";
+        assert_eq!(Ok(String::from(expected)), report_with_width(input, Width::I64, Report::Passes, 2));
+
+        let expected = "pass,line,frequency\n2,2,2\n";
+        assert_eq!(Ok(String::from(expected)), report_with_width(input, Width::Big, Report::Repeats, 2));
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = "+1\n-2\n\n+3\n+1\n-3\n+1\n";
+        let steps: Vec<Step<i64>> = calibrate_stream(input.as_bytes()).map(|step| step.unwrap()).collect();
+
+        let frequencies: Vec<i64> = steps.iter().map(|step| step.frequency).collect();
+        assert_eq!(vec![1, -1, 2, 3, 0, 1], frequencies);
+
+        let repeats: Vec<usize> = steps.iter().filter(|step| step.first_repeat).map(|step| step.line).collect();
+        assert_eq!(vec![6], repeats);
+        assert_eq!("1", steps[0].to_string());
+        assert_eq!("0 (first repeat)", steps[4].to_string());
+    }
+
+    #[test]
+    fn test_stream_stops_at_bad_line() {
+        let mut steps = calibrate_stream::<_, i64>("+1\n  plus two\n+3\n".as_bytes());
+        assert_eq!(Some(Ok(Step { line: 1, frequency: 1, first_repeat: false })), steps.next());
+        match steps.next() {
+            Some(Err(Error::Parse(errors))) => assert_eq!((2, 3), (errors[0].line, errors[0].column)),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert_eq!(None, steps.next());
+
+        let mut steps = calibrate_stream::<_, i64>("+9223372036854775807\n+1\n".as_bytes());
+        assert!(steps.next().unwrap().is_ok());
+        assert_eq!(Some(Err(overflow_error::<i64>(2))), steps.next());
+    }
+
+    /// Changes that go round and round forever.
+    struct Forever {
+        changes: &'static [u8],
+        position: usize,
+    }
+
+    impl io::Read for Forever {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let mut read = 0;
+            while read < buffer.len() {
+                buffer[read] = self.changes[self.position];
+                self.position = (self.position + 1) % self.changes.len();
+                read += 1;
+            }
+            Ok(read)
+        }
+    }
+
+    #[test]
+    fn test_stream_never_ending() {
+        let input = io::BufReader::new(Forever { changes: b"+3\n+3\n+4\n-2\n-4\n", position: 0 });
+        let repeat = calibrate_stream::<_, BigInt>(input)
+            .map(|step| step.unwrap())
+            .find(|step| step.first_repeat)
+            .unwrap();
+        assert_eq!(Step { line: 7, frequency: BigInt::from(10), first_repeat: true }, repeat);
+
+        let input = io::BufReader::new(Forever { changes: b"+1\n", position: 0 });
+        let last = calibrate_stream::<_, i64>(input).take(100_000).last().unwrap().unwrap();
+        assert_eq!(100_000, last.frequency);
+    }
+}
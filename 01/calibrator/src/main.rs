@@ -1,15 +1,64 @@
-use std::fs;
+extern crate aoc_common;
+extern crate calibrator;
+extern crate num_bigint;
+
+use calibrator::{Frequency, Width};
+use num_bigint::BigInt;
+use std::env;
+use std::io;
+use std::io::{BufRead, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage: calibrator [--width i64|i128|big] [--report passes|repeats [--passes <passes>] | --stream] [input]
+
+A report prints CSV describing the frequency's walk over one pass of the
+changes, or as many passes as asked for, in place of the answer.
+
+--stream prints the frequency after every change as soon as it is read,
+marking the first repeat, for input that keeps coming such as a pipe.";
 
 fn main() {
-    let input_filename = String::from("input.txt");
-    let input = fs::read_to_string(input_filename)
-        .expect("Failed to read file");
-
-    let mut total_calibration = 0;
-    for line in input.lines() {
-        let calibration: i32 = line.parse().unwrap();
-        total_calibration += calibration
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match calibrator::parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    if options.stream {
+        let input = aoc_common::open_input_from(options.input.as_deref());
+        match options.width {
+            Width::I64 => print_stream::<i64>(input),
+            Width::I128 => print_stream::<i128>(input),
+            Width::Big => print_stream::<BigInt>(input),
+        }
+        return;
+    }
+
+    let input = aoc_common::read_input_from(options.input.as_deref());
+
+    if let Some(report) = options.report {
+        let passes = options.passes.unwrap_or(1);
+        print!("{}", aoc_common::unwrap_or_exit(calibrator::report_with_width(&input, options.width, report, passes)));
+        return;
     }
 
-    println!("Final calibration: {}\n", total_calibration);
+    let total_calibration = aoc_common::unwrap_or_exit(calibrator::calibrate_with_width(&input, options.width));
+
+    aoc_common::print_result(total_calibration);
+}
+
+fn print_stream<T: Frequency>(input: Box<dyn BufRead>) {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    for step in calibrator::calibrate_stream::<_, T>(input) {
+        let step = aoc_common::unwrap_or_exit(step);
+
+        // Whatever is reading has stopped listening, as `head` does
+        if writeln!(stdout, "{}", step).and_then(|_| stdout.flush()).is_err() {
+            return;
+        }
+    }
 }
@@ -0,0 +1,364 @@
+extern crate aoc_common;
+extern crate calibrator;
+extern crate num_bigint;
+
+use aoc_common::{Answer, Error, Solution};
+use calibrator::{Change, Frequency, Report, Width};
+use num_bigint::BigInt;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(calibrate_from_lines(input.to_string())?.into())
+    }
+}
+
+pub fn calibrate_from_lines(input: String) -> Result<i64, Error> {
+    first_repeat_as(&input)
+}
+
+/// Finds the first frequency reached twice, checking each step fits.
+pub fn first_repeat_as<T: Frequency>(input: &str) -> Result<T, Error> {
+    let changes = calibrator::read_changes::<T>(input)?;
+    if changes.is_empty() {
+        return Err(Error::Invalid(String::from("there are no frequency changes")));
+    }
+
+    match find_first_repeat(&changes)? {
+        Some(repeat) => Ok(repeat.frequency),
+        None => Err(Error::Invalid(String::from("the frequency never repeats"))),
+    }
+}
+
+/// The first frequency reached twice, and when it was reached.
+#[derive(Debug, PartialEq)]
+pub struct Repeat<T> {
+    pub frequency: T,
+    /// How many times the whole list had been gone through before reaching
+    /// it, so 0 for a repeat in the first pass.
+    pub passes: BigInt,
+    /// The line of the change that reached it.
+    pub line: usize,
+}
+
+/// Finds the first frequency reached twice, or `None` if none ever is.
+///
+/// Rather than going round the list until something repeats, this goes
+/// round once. Each pass after the first moves every frequency along by the
+/// drift, the total of all the changes, so a frequency `a` from the first
+/// pass becomes `a + drift`, `a + 2 * drift` and so on. That can only land
+/// on a frequency `b` from the first pass if `b - a` is a multiple of the
+/// drift, so the frequencies are grouped by their remainder modulo the
+/// drift and sorted. In each group a frequency first lands on the next one
+/// along in the direction of the drift, and whichever does so in the fewest
+/// passes, or earliest in the pass, is the first repeat. If no frequency
+/// has another ahead of it in its group, nothing ever repeats.
+pub fn find_first_repeat<T: Frequency>(changes: &[Change<T>]) -> Result<Option<Repeat<T>>, Error> {
+    // Anything can repeat in the first pass, so play it out
+    let mut seen = HashSet::new();
+    let mut frequencies = Vec::with_capacity(changes.len());
+    let mut frequency = T::zero();
+    seen.insert(frequency.clone());
+    for change in changes {
+        frequency = change.apply_to(&frequency)?;
+        if !seen.insert(frequency.clone()) {
+            return Ok(Some(Repeat { frequency, passes: BigInt::from(0), line: change.line }));
+        }
+        frequencies.push(frequency.clone());
+    }
+
+    // A drift of zero would have brought the last frequency back to the
+    // start, so it can only happen when there are no changes at all
+    let drift: BigInt = frequency.into();
+    if drift == BigInt::from(0) {
+        return Ok(None);
+    }
+
+    // Turn everything round when the drift is negative, so that frequencies
+    // always climb from pass to pass
+    let direction = if drift < BigInt::from(0) { BigInt::from(-1) } else { BigInt::from(1) };
+    let step = &drift * &direction;
+    let climbed: Vec<BigInt> = frequencies.iter()
+        .map(|frequency| frequency.clone().into() * &direction)
+        .collect();
+
+    // The starting frequency can be landed on, but doesn't move on itself
+    // as it's the same as the last frequency of the pass before the first
+    let mut groups: HashMap<BigInt, Vec<(BigInt, Option<usize>)>> = HashMap::new();
+    let start = (BigInt::from(0), None);
+    let others = climbed.iter().enumerate().map(|(index, frequency)| (frequency.clone(), Some(index)));
+    for (frequency, index) in Some(start).into_iter().chain(others) {
+        let remainder = ((&frequency % &step) + &step) % &step;
+        groups.entry(remainder).or_default().push((frequency, index));
+    }
+
+    // The passes taken, the change taken on, and the frequency landed on
+    let mut first: Option<(BigInt, usize, Option<usize>)> = None;
+    for group in groups.values_mut() {
+        group.sort();
+        for pair in group.windows(2) {
+            let (ref from, from_index) = pair[0];
+            let (ref to, to_index) = pair[1];
+            let from_index = match from_index {
+                Some(index) => index,
+                None => continue,
+            };
+
+            let passes = (to - from) / &step;
+            let sooner = match first {
+                Some((ref first_passes, first_index, _)) => (&passes, from_index) < (first_passes, first_index),
+                None => true,
+            };
+            if sooner {
+                first = Some((passes, from_index, to_index));
+            }
+        }
+    }
+
+    let (passes, index, landed_on) = match first {
+        Some(first) => first,
+        None => return Ok(None),
+    };
+
+    if let Some(line) = first_overflow::<T>(changes, &climbed, &direction, &step, &passes, index) {
+        return Err(calibrator::overflow_error::<T>(line));
+    }
+
+    let frequency = match landed_on {
+        Some(index) => frequencies[index].clone(),
+        None => T::zero(),
+    };
+    Ok(Some(Repeat { frequency, passes, line: changes[index].line }))
+}
+
+/// Finds the line of the first change to take the frequency further than
+/// `T` can hold, on the way to change `last` of pass `passes`. Frequencies
+/// only get further from the start with each pass, so a search over the
+/// passes finds the first pass to go too far.
+fn first_overflow<T: Frequency>(changes: &[Change<T>], climbed: &[BigInt], direction: &BigInt, step: &BigInt, passes: &BigInt, last: usize) -> Option<usize> {
+    let fits = |frequency: &BigInt| T::try_from(frequency * direction).is_ok();
+    let highest = climbed.iter().max().unwrap();
+    let pass_fits = |pass: &BigInt| fits(&(highest + pass * step));
+    let first_line_too_far = |pass: &BigInt, count: usize| {
+        (0..count).find(|index| !fits(&(&climbed[*index] + pass * step)))
+            .map(|index| changes[index].line)
+    };
+
+    // Every pass before the last is gone through in full
+    let last_full_pass = passes - 1;
+    if !pass_fits(&last_full_pass) {
+        let mut low = BigInt::from(1);
+        let mut high = last_full_pass;
+        while low < high {
+            let middle = (&low + &high) / 2;
+            if pass_fits(&middle) {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        return first_line_too_far(&low, changes.len());
+    }
+
+    first_line_too_far(passes, last + 1)
+}
+
+/// Finds the first frequency reached twice in the chosen width.
+pub fn first_repeat_with_width(input: &str, width: Width) -> Result<String, Error> {
+    match width {
+        Width::I64 => Ok(first_repeat_as::<i64>(input)?.to_string()),
+        Width::I128 => Ok(first_repeat_as::<i128>(input)?.to_string()),
+        Width::Big => Ok(first_repeat_as::<BigInt>(input)?.to_string()),
+    }
+}
+
+/// The most passes a report goes through to reach the first repeat without
+/// being told to.
+const MAX_REPORT_PASSES: usize = 100_000;
+
+/// Traces the frequency in the chosen width, as the CSV for a report. Unless
+/// told how many passes to cover, the report goes to the end of the pass
+/// holding the first repeat.
+pub fn report_with_width(input: &str, width: Width, report: Report, passes: Option<usize>) -> Result<String, Error> {
+    match width {
+        Width::I64 => report_as::<i64>(input, report, passes),
+        Width::I128 => report_as::<i128>(input, report, passes),
+        Width::Big => report_as::<BigInt>(input, report, passes),
+    }
+}
+
+fn report_as<T: Frequency>(input: &str, report: Report, passes: Option<usize>) -> Result<String, Error> {
+    let changes = calibrator::read_changes::<T>(input)?;
+    let passes = match passes {
+        Some(passes) => passes,
+        None => passes_to_first_repeat(&changes)?,
+    };
+
+    Ok(calibrator::trace(&changes, passes)?.to_csv(report))
+}
+
+fn passes_to_first_repeat<T: Frequency>(changes: &[Change<T>]) -> Result<usize, Error> {
+    let repeat = match find_first_repeat(changes)? {
+        Some(repeat) => repeat,
+        None => return Err(Error::Invalid(String::from("the frequency never repeats, so give --passes to say how far to trace it"))),
+    };
+
+    let passes = repeat.passes + 1;
+    match usize::try_from(&passes) {
+        Ok(passes) if passes <= MAX_REPORT_PASSES => Ok(passes),
+        _ => Err(Error::Invalid(format!("the first repeat is {} passes in, so give --passes to trace fewer", passes))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_no_trailing_newline() {
+        let input_lines = String::from("+1\n-1");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(0, result);
+    }
+
+    #[test]
+    fn test_with_trailing_newlines() {
+        let input_lines = String::from("+1\n-1\n\n\n");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(0, result);
+    }
+
+    #[test]
+    fn test_with_trailing_newlines_and_whitespace() {
+        let input_lines = String::from("+1\n-1\n  \n\n");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(0, result);
+    }
+
+    #[test]
+    fn test_looping_1() {
+        let input_lines = String::from("+1\n-1\n");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(0, result);
+    }
+
+    #[test]
+    fn test_looping_2() {
+        let input_lines = String::from("+3\n+3\n+4\n-2\n-4\n");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(10, result);
+    }
+
+    #[test]
+    fn test_looping_3() {
+        let input_lines = String::from("-6\n+3\n+8\n+5\n-6\n");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(5, result);
+    }
+
+    #[test]
+    fn test_looping_4() {
+        let input_lines = String::from("+7\n+7\n-2\n-7\n-4\n");
+        let result = calibrate_from_lines(input_lines).unwrap();
+
+        assert_eq!(14, result);
+    }
+
+    #[test]
+    fn test_overflow_on_a_later_pass() {
+        // Repeats 5 * 2^60 after four passes, but the fourth goes past 2^63
+        let input = "+5764607523034234880\n-4611686018427387904\n";
+        let expected = Error::Invalid(String::from("the frequency no longer fits in a 64 bit integer after line 1"));
+        assert_eq!(Err(expected), calibrate_from_lines(String::from(input)));
+        assert_eq!(Ok(5764607523034234880), first_repeat_as::<i128>(input));
+    }
+
+    #[test]
+    fn test_widths() {
+        let input = "+9223372036854775807\n+1\n-1\n";
+        assert!(first_repeat_with_width(input, Width::I64).is_err());
+        assert_eq!(Ok(String::from("9223372036854775807")), first_repeat_with_width(input, Width::I128));
+        assert_eq!(Ok(String::from("9223372036854775807")), first_repeat_with_width(input, Width::Big));
+    }
+
+    #[test]
+    fn test_never_repeats() {
+        let expected = Err(Error::Invalid(String::from("the frequency never repeats")));
+        assert_eq!(expected, calibrate_from_lines(String::from("+1\n")));
+        assert_eq!(expected, calibrate_from_lines(String::from("+3\n-1\n")));
+        assert_eq!(expected, calibrate_from_lines(String::from("+1\n+1\n")));
+    }
+
+    #[test]
+    fn test_no_changes() {
+        assert!(calibrate_from_lines(String::from("\n")).is_err());
+    }
+
+    #[test]
+    fn test_where_the_repeat_is() {
+        let changes = calibrator::read_changes::<i64>("+3\n+3\n+4\n-2\n-4\n").unwrap();
+        let expected = Repeat { frequency: 10, passes: BigInt::from(1), line: 2 };
+        assert_eq!(Some(expected), find_first_repeat(&changes).unwrap());
+
+        // A repeat in the first pass
+        let changes = calibrator::read_changes::<i64>("+1\n-1\n").unwrap();
+        let expected = Repeat { frequency: 0, passes: BigInt::from(0), line: 2 };
+        assert_eq!(Some(expected), find_first_repeat(&changes).unwrap());
+    }
+
+    #[test]
+    fn test_negative_drift() {
+        // The mirror image of the second example
+        let result = calibrate_from_lines(String::from("-3\n-3\n-4\n+2\n+4\n")).unwrap();
+        assert_eq!(-10, result);
+    }
+
+    #[test]
+    fn test_report_goes_to_the_first_repeat() {
+        let input = "+3\n+3\n+4\n-2\n-4\n";
+        let expected = "pass,line,frequency\n2,2,10\n2,5,8\n";
+        assert_eq!(Ok(String::from(expected)), report_with_width(input, Width::I64, Report::Repeats, None));
+
+        let expected = "pass,drift,lowest,highest\n1,4,3,10\n";
+        assert_eq!(Ok(String::from(expected)), report_with_width(input, Width::I64, Report::Passes, Some(1)));
+    }
+
+    #[test]
+    fn test_report_too_many_passes() {
+        assert!(report_with_width("+1\n", Width::I64, Report::Passes, None).is_err());
+        assert!(report_with_width("+1000000000\n-999999999\n", Width::I64, Report::Passes, None).is_err());
+        assert!(report_with_width("+1\n", Width::I64, Report::Passes, Some(3)).is_ok());
+    }
+
+    #[test]
+    fn test_repeat_many_passes_away() {
+        // Climbs by one each pass until it gets back up to a million
+        let result = calibrate_from_lines(String::from("+1000000\n-999999\n")).unwrap();
+        assert_eq!(1000000, result);
+    }
+}
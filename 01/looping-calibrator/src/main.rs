@@ -1,111 +1,38 @@
-use std::fs;
-use std::collections::HashSet;
+extern crate aoc_common;
+extern crate calibrator;
+extern crate looping_calibrator;
 
-fn main() {
-    let input = read_input_lines();
-
-    let total_calibration = calibrate_from_lines(input);
-
-    println!("Final calibration: {}\n", total_calibration);
-}
-
-fn read_input_lines() -> String {
-    let input_filename = String::from("input.txt");
-    fs::read_to_string(input_filename)
-        .expect("Failed to read file")
-}
-
-
-fn calibrate_from_lines(input: String) -> i32 {
-    let mut seen = HashSet::new();
-    let mut total_calibration = 0;
-    seen.insert(total_calibration);
+use std::env;
+use std::process;
 
-    let mut loop_count = 0;
-    let max_loops = 10_000;
-    loop {
-        loop_count += 1;
-        if loop_count >= max_loops {
-            panic!("No repeat found after looping over all input {} times!", max_loops);
-        }
-
-        for line in input.lines() {
-            let line = line.trim();
-            if line.len() == 0 {
-                continue;
-            }
+const USAGE: &str = "\
+Usage: looping-calibrator [--width i64|i128|big] [--report passes|repeats [--passes <passes>]] [input]
 
-            let calibration: i32 = line.parse().unwrap();
-            total_calibration += calibration;
+A report prints CSV describing the frequency's walk, up to the end of the
+pass with the first repeat in or over as many passes as asked for, in
+place of the answer.";
 
-            if seen.contains(&total_calibration) {
-                // Found the first repeated value, finish
-                return total_calibration;
-            }
-
-            seen.insert(total_calibration);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match calibrator::parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
         }
+    };
+    if options.stream {
+        eprintln!("A stream can't be gone round more than once, use calibrator --stream\n\n{}", USAGE);
+        process::exit(2);
     }
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_with_no_trailing_newline() {
-        let input_lines = String::from("+1\n-1");
-        let result = calibrate_from_lines(input_lines);
-
-        assert_eq!(0, result);
-    }
-
-    #[test]
-    fn test_with_trailing_newlines() {
-        let input_lines = String::from("+1\n-1\n\n\n");
-        let result = calibrate_from_lines(input_lines);
-
-        assert_eq!(0, result);
-    }
-
-    #[test]
-    fn test_with_trailing_newlines_and_whitespace() {
-        let input_lines = String::from("+1\n-1\n  \n\n");
-        let result = calibrate_from_lines(input_lines);
-
-        assert_eq!(0, result);
-    }
+    let input = aoc_common::read_input_from(options.input.as_deref());
 
-    #[test]
-    fn test_looping_1() {
-        let input_lines = String::from("+1\n-1\n");
-        let result = calibrate_from_lines(input_lines);
-
-        assert_eq!(0, result);
-    }
-
-    #[test]
-    fn test_looping_2() {
-        let input_lines = String::from("+3\n+3\n+4\n-2\n-4\n");
-        let result = calibrate_from_lines(input_lines);
-
-        assert_eq!(10, result);
+    if let Some(report) = options.report {
+        print!("{}", aoc_common::unwrap_or_exit(looping_calibrator::report_with_width(&input, options.width, report, options.passes)));
+        return;
     }
 
-    #[test]
-    fn test_looping_3() {
-        let input_lines = String::from("-6\n+3\n+8\n+5\n-6\n");
-        let result = calibrate_from_lines(input_lines);
+    let total_calibration = aoc_common::unwrap_or_exit(looping_calibrator::first_repeat_with_width(&input, options.width));
 
-        assert_eq!(5, result);
-    }
-
-    #[test]
-    fn test_looping_4() {
-        let input_lines = String::from("+7\n+7\n-2\n-7\n-4\n");
-        let result = calibrate_from_lines(input_lines);
-
-        assert_eq!(14, result);
-    }
+    aoc_common::print_result(total_calibration);
 }
@@ -0,0 +1,236 @@
+//! How far apart box IDs are, and finding the IDs that might be close to
+//! one another without comparing every pair.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// How to count the differences between two IDs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    /// How many positions the IDs have different letters at. Letters past
+    /// the end of the shorter ID count as differences.
+    Hamming,
+    /// The fewest letters to insert, delete or swap for another to turn one
+    /// ID into the other.
+    Levenshtein,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Metric, String> {
+        match string {
+            "hamming" => Ok(Metric::Hamming),
+            "levenshtein" => Ok(Metric::Levenshtein),
+            other => Err(format!("Unknown metric '{}', expected hamming or levenshtein", other)),
+        }
+    }
+}
+
+impl Metric {
+    /// How far apart two IDs are.
+    pub fn distance<T: PartialEq>(&self, id: &[T], other_id: &[T]) -> usize {
+        match self {
+            Metric::Hamming => hamming(id, other_id),
+            Metric::Levenshtein => levenshtein(id, other_id),
+        }
+    }
+
+    /// How far apart two IDs are, and the letters they have in common once
+    /// lined up with each other.
+    pub fn align<T: PartialEq + Clone>(&self, id: &[T], other_id: &[T]) -> (usize, Vec<T>) {
+        match self {
+            Metric::Hamming => {
+                let common = id.iter().zip(other_id.iter())
+                    .filter(|(a, b)| a == b)
+                    .map(|(a, _)| a.clone())
+                    .collect();
+                (hamming(id, other_id), common)
+            },
+            Metric::Levenshtein => levenshtein_align(id, other_id),
+        }
+    }
+}
+
+fn hamming<T: PartialEq>(id: &[T], other_id: &[T]) -> usize {
+    let different = id.iter().zip(other_id.iter()).filter(|(a, b)| a != b).count();
+    let longer = id.len().max(other_id.len());
+    let shorter = id.len().min(other_id.len());
+    different + longer - shorter
+}
+
+/// Works through the table of distances between every start of one ID and
+/// every start of the other, a row at a time.
+fn levenshtein<T: PartialEq>(id: &[T], other_id: &[T]) -> usize {
+    let mut row: Vec<usize> = (0..=other_id.len()).collect();
+    for (i, a) in id.iter().enumerate() {
+        let mut next = vec![i + 1; other_id.len() + 1];
+        for (j, b) in other_id.iter().enumerate() {
+            let swap = row[j] + if a == b { 0 } else { 1 };
+            next[j + 1] = swap.min(row[j + 1] + 1).min(next[j] + 1);
+        }
+        row = next;
+    }
+    row[other_id.len()]
+}
+
+/// Fills in the whole table of distances, then follows it back from the end
+/// to find which letters were kept as they were.
+fn levenshtein_align<T: PartialEq + Clone>(id: &[T], other_id: &[T]) -> (usize, Vec<T>) {
+    let width = other_id.len() + 1;
+    let mut table = vec![0; (id.len() + 1) * width];
+    for i in 0..=id.len() {
+        for j in 0..=other_id.len() {
+            table[i * width + j] = if i == 0 {
+                j
+            } else if j == 0 {
+                i
+            } else {
+                let swap = table[(i - 1) * width + j - 1] + if id[i - 1] == other_id[j - 1] { 0 } else { 1 };
+                swap.min(table[(i - 1) * width + j] + 1).min(table[i * width + j - 1] + 1)
+            };
+        }
+    }
+
+    let mut common = Vec::new();
+    let (mut i, mut j) = (id.len(), other_id.len());
+    while i > 0 && j > 0 {
+        let here = table[i * width + j];
+        if id[i - 1] == other_id[j - 1] && here == table[(i - 1) * width + j - 1] {
+            common.push(id[i - 1].clone());
+            i -= 1;
+            j -= 1;
+        } else if here == table[(i - 1) * width + j - 1] + 1 {
+            i -= 1;
+            j -= 1;
+        } else if here == table[(i - 1) * width + j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+
+    common.reverse();
+    (table[id.len() * width + other_id.len()], common)
+}
+
+/// Where each of the `within + 1` pieces an ID of `length` letters is cut
+/// into starts, and how long it is. Later pieces are a letter longer when
+/// the letters don't divide evenly.
+fn pieces(length: usize, within: usize) -> Vec<(usize, usize)> {
+    let count = within + 1;
+    let shorter = length / count;
+    let longer_from = count - length % count;
+
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for piece in 0..count {
+        let piece_length = if piece < longer_from { shorter } else { shorter + 1 };
+        pieces.push((start, piece_length));
+        start += piece_length;
+    }
+    return pieces;
+}
+
+/// Finds every pair of IDs that could be no further than `within` apart,
+/// by either metric, as where they are in the list with the earlier first.
+///
+/// Each ID is cut into `within + 1` pieces. Every edit spoils at most one
+/// piece, so IDs that close always share a piece, found no more than
+/// `within` letters from where it was cut from. The pieces of every ID are
+/// filed away, shortest IDs first, and each ID looks up the letters around
+/// where the pieces of shorter or equally long IDs were cut from. Only the
+/// IDs found that way need measuring. A Hamming distance is never less than
+/// the Levenshtein distance, so the same pairs serve for both.
+pub fn candidate_pairs<T: Hash + Eq>(ids: &[Vec<T>], within: usize) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..ids.len()).collect();
+    order.sort_by_key(|index| ids[*index].len());
+
+    let mut filed: HashMap<(usize, usize, &[T]), Vec<usize>> = HashMap::new();
+    let mut pairs = Vec::new();
+    for index in order {
+        let id = &ids[index];
+
+        let mut found = HashSet::new();
+        for length in id.len().saturating_sub(within)..=id.len() {
+            for (piece, (start, piece_length)) in pieces(length, within).into_iter().enumerate() {
+                if piece_length > id.len() {
+                    continue;
+                }
+                let earliest = start.saturating_sub(within);
+                let latest = (start + within).min(id.len() - piece_length);
+                for position in earliest..=latest {
+                    let key = (length, piece, &id[position..(position + piece_length)]);
+                    if let Some(others) = filed.get(&key) {
+                        found.extend(others.iter().cloned());
+                    }
+                }
+            }
+        }
+        for other in found {
+            pairs.push((other.min(index), other.max(index)));
+        }
+
+        for (piece, (start, piece_length)) in pieces(id.len(), within).into_iter().enumerate() {
+            filed.entry((id.len(), piece, &id[start..(start + piece_length)])).or_default().push(index);
+        }
+    }
+
+    pairs.sort();
+    return pairs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn test_hamming() {
+        assert_eq!(0, Metric::Hamming.distance(&chars("abcde"), &chars("abcde")));
+        assert_eq!(2, Metric::Hamming.distance(&chars("fghij"), &chars("fguix")));
+        assert_eq!(3, Metric::Hamming.distance(&chars("abcde"), &chars("abx")));
+        assert_eq!((3, chars("ab")), Metric::Hamming.align(&chars("abx"), &chars("abcde")));
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(3, Metric::Levenshtein.distance(&chars("kitten"), &chars("sitting")));
+        assert_eq!(5, Metric::Levenshtein.distance(&chars(""), &chars("abcde")));
+        assert_eq!(1, Metric::Levenshtein.distance(&chars("abcde"), &chars("bcde")));
+        assert_eq!((3, chars("ittn")), Metric::Levenshtein.align(&chars("kitten"), &chars("sitting")));
+        assert_eq!((2, chars("abcd")), Metric::Levenshtein.align(&chars("xabcd"), &chars("abcdy")));
+    }
+
+    #[test]
+    fn test_pieces() {
+        assert_eq!(vec![(0, 2), (2, 2), (4, 3)], pieces(7, 2));
+        assert_eq!(vec![(0, 5)], pieces(5, 0));
+        assert_eq!(vec![(0, 0), (0, 1), (1, 1)], pieces(2, 2));
+    }
+
+    #[test]
+    fn test_candidate_pairs() {
+        let ids: Vec<Vec<char>> = ["book", "books", "cake", "boo", "cape", "cart", "boon", "book", ""].iter()
+            .map(|id| chars(id))
+            .collect();
+
+        let pairs = candidate_pairs(&ids, 1);
+        for expected in [(0, 1), (0, 3), (0, 6), (0, 7), (1, 7), (2, 4), (3, 6), (3, 7), (6, 7)].iter() {
+            assert!(pairs.contains(expected), "missing {:?}", expected);
+        }
+        assert!(!pairs.contains(&(0, 2)));
+        assert!(candidate_pairs(&ids, 0).iter().all(|(a, b)| ids[*a] == ids[*b]));
+    }
+
+    #[test]
+    fn test_parse_metric() {
+        assert_eq!(Ok(Metric::Hamming), "hamming".parse());
+        assert_eq!(Ok(Metric::Levenshtein), "levenshtein".parse());
+        assert!("euclid".parse::<Metric>().is_err());
+    }
+}
@@ -0,0 +1,267 @@
+#![allow(clippy::needless_return)]
+
+extern crate aoc_common;
+extern crate ims;
+
+pub mod distance;
+
+use aoc_common::{Answer, Error, Solution};
+use distance::Metric;
+use ims::letters::Letters;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process_lines(input.to_string()).into())
+    }
+}
+
+pub fn process_lines(input: String) -> String {
+    common_letters(&input, &Letters::default())
+}
+
+/// The letters shared by the first two box IDs that differ by exactly one
+/// letter, or nothing if no two do.
+pub fn common_letters(input: &str, letters: &Letters) -> String {
+    let box_ids: Vec<&str> = aoc_common::lines(input).collect();
+
+    match near_matches(&box_ids, letters).into_iter().next() {
+        Some(near_match) => near_match.common,
+        None => String::new(),
+    }
+}
+
+/// Two box IDs that differ by exactly one letter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearMatch {
+    /// Where the first ID is in the list, counting from 0.
+    pub first: usize,
+    /// Where the second ID is in the list, always after the first.
+    pub second: usize,
+    /// Which letter of the IDs differs, counting from 0.
+    pub position: usize,
+    /// The letters the IDs have in common.
+    pub common: String,
+}
+
+/// The multiplier for hashing the letters of an ID.
+const HASH_BASE: u64 = 1_000_003;
+
+fn letter_hash(letter: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    letter.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Finds every pair of IDs that differ by exactly one letter, ordered by
+/// where they are in the list, with the IDs split into letters by
+/// `letters`.
+///
+/// Rather than comparing every pair, each ID is filed under a hash of
+/// itself with each letter in turn left out, and the filing sorted. IDs one
+/// letter apart end up next to each other under the position they differ
+/// at, so only IDs sharing a hash need comparing, and copies of the same ID
+/// only once between them. Each hash is worked out from the hash of the
+/// whole ID by taking away the left out letter, so filing an ID costs time
+/// in proportion to its length.
+pub fn near_matches(ids: &[&str], letters: &Letters) -> Vec<NearMatch> {
+    let ids: Vec<Vec<String>> = ids.iter().map(|id| letters.split(id)).collect();
+
+    let mut filed = Vec::new();
+    for (index, id) in ids.iter().enumerate() {
+        let mut powers = vec![1u64; id.len()];
+        for position in (0..id.len().saturating_sub(1)).rev() {
+            powers[position] = powers[position + 1].wrapping_mul(HASH_BASE);
+        }
+
+        let hashes: Vec<u64> = id.iter().map(|letter| letter_hash(letter)).collect();
+        let hash = hashes.iter()
+            .fold(0u64, |hash, letter| hash.wrapping_mul(HASH_BASE).wrapping_add(*letter));
+        for position in 0..id.len() {
+            let masked = hash.wrapping_sub(hashes[position].wrapping_mul(powers[position]));
+            filed.push((id.len(), position, masked, index));
+        }
+    }
+    filed.sort_unstable();
+
+    let mut matches = Vec::new();
+    for group in filed.chunk_by(|a, b| (a.0, a.1, a.2) == (b.0, b.1, b.2)) {
+        let position = group[0].1;
+
+        // Identical IDs share every hash without ever matching, so each
+        // different ID is compared once for all its copies
+        let mut distinct: Vec<Vec<usize>> = Vec::new();
+        let mut seen: HashMap<&[String], usize> = HashMap::new();
+        for (_, _, _, index) in group {
+            let slot = *seen.entry(&ids[*index]).or_insert_with(|| {
+                distinct.push(Vec::new());
+                distinct.len() - 1
+            });
+            distinct[slot].push(*index);
+        }
+
+        for (which, copies) in distinct.iter().enumerate() {
+            for other_copies in &distinct[(which + 1)..] {
+                // Unrelated IDs can share a hash too
+                if let Some(common) = common_letters_but_one(&ids[copies[0]], &ids[other_copies[0]], position) {
+                    for first in copies {
+                        for second in other_copies {
+                            let (first, second) = (*first.min(second), *first.max(second));
+                            matches.push(NearMatch { first, second, position, common: common.clone() });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    matches.sort_by_key(|near_match| (near_match.first, near_match.second));
+    return matches;
+}
+
+/// The letters two IDs have in common, if they differ at `position` and
+/// nowhere else.
+fn common_letters_but_one(id: &[String], other_id: &[String], position: usize) -> Option<String> {
+    if id[position] == other_id[position] {
+        return None;
+    }
+
+    let mut common = String::new();
+    for (index, (a, b)) in id.iter().zip(other_id.iter()).enumerate() {
+        if index == position {
+            continue;
+        }
+        if a != b {
+            return None;
+        }
+        common += a;
+    }
+    return Some(common);
+}
+
+/// Two box IDs no further apart than asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseMatch {
+    /// Where the first ID is in the list, counting from 0.
+    pub first: usize,
+    /// Where the second ID is in the list, always after the first.
+    pub second: usize,
+    /// How far apart the IDs are.
+    pub distance: usize,
+    /// The letters the IDs have in common once lined up with each other.
+    pub common: String,
+}
+
+/// Finds every pair of IDs no further than `within` apart by `metric`,
+/// ordered by where they are in the list, with the IDs split into letters
+/// by `letters`. Identical IDs are no distance apart, so are always found.
+pub fn close_matches(ids: &[&str], metric: Metric, within: usize, letters: &Letters) -> Vec<CloseMatch> {
+    let ids: Vec<Vec<String>> = ids.iter().map(|id| letters.split(id)).collect();
+
+    let mut matches = Vec::new();
+    for (first, second) in distance::candidate_pairs(&ids, within) {
+        let (distance, common) = metric.align(&ids[first], &ids[second]);
+        if distance <= within {
+            matches.push(CloseMatch { first, second, distance, common: common.concat() });
+        }
+    }
+    return matches;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = String::from("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n");
+        let result = process_lines(input);
+        assert_eq!("fgij", result);
+    }
+
+    #[test]
+    fn test_near_matches() {
+        let ids = ["abcde", "fghij", "abxde", "fguij", "abcde", "abyde", "abc"];
+        let expected = vec![
+            NearMatch { first: 0, second: 2, position: 2, common: String::from("abde") },
+            NearMatch { first: 0, second: 5, position: 2, common: String::from("abde") },
+            NearMatch { first: 1, second: 3, position: 2, common: String::from("fgij") },
+            NearMatch { first: 2, second: 4, position: 2, common: String::from("abde") },
+            NearMatch { first: 2, second: 5, position: 2, common: String::from("abde") },
+            NearMatch { first: 4, second: 5, position: 2, common: String::from("abde") },
+        ];
+        assert_eq!(expected, near_matches(&ids, &Letters::default()));
+    }
+
+    #[test]
+    fn test_near_matches_many_copies() {
+        let mut ids = vec!["abcde"; 5000];
+        ids.insert(2, "abxde");
+        let found = near_matches(&ids, &Letters::default());
+        assert_eq!(5000, found.len());
+        assert_eq!(NearMatch { first: 0, second: 2, position: 2, common: String::from("abde") }, found[0]);
+        assert_eq!((2, 3), (found[2].first, found[2].second));
+        assert!(found.iter().all(|near_match| near_match.first == 2 || near_match.second == 2));
+    }
+
+    #[test]
+    fn test_near_matches_non_ascii() {
+        let ids = ["héllo", "hallo", "hëllo"];
+        let positions: Vec<(usize, usize, usize)> = near_matches(&ids, &Letters::default()).iter()
+            .map(|near_match| (near_match.first, near_match.second, near_match.position))
+            .collect();
+        assert_eq!(vec![(0, 1, 1), (0, 2, 1), (1, 2, 1)], positions);
+        assert!(near_matches(&["", "a", "b"], &Letters::default()).iter().all(|near_match| near_match.common.is_empty()));
+    }
+
+    #[test]
+    fn test_close_matches() {
+        let ids = ["abcde", "abxde", "bcdef", "abcde", "zzzzz"];
+
+        let found: Vec<(usize, usize, usize)> = close_matches(&ids, Metric::Hamming, 1, &Letters::default()).iter()
+            .map(|close_match| (close_match.first, close_match.second, close_match.distance))
+            .collect();
+        assert_eq!(vec![(0, 1, 1), (0, 3, 0), (1, 3, 1)], found);
+
+        let found = close_matches(&ids, Metric::Levenshtein, 2, &Letters::default());
+        let expected = CloseMatch { first: 0, second: 2, distance: 2, common: String::from("bcde") };
+        assert_eq!(Some(&expected), found.iter().find(|close_match| (close_match.first, close_match.second) == (0, 2)));
+        assert_eq!(5, found.len());
+    }
+
+    #[test]
+    fn test_matches_by_grapheme() {
+        // The accented e is two chars but one grapheme, so these differ by
+        // two chars but only one letter
+        let ids = ["cafe\u{301}", "cafx"];
+        assert!(near_matches(&ids, &Letters::default()).is_empty());
+
+        let letters = Letters { graphemes: true, ..Letters::default() };
+        let expected = vec![NearMatch { first: 0, second: 1, position: 3, common: String::from("caf") }];
+        assert_eq!(expected, near_matches(&ids, &letters));
+
+        let ids = ["Straße", "STRASSE"];
+        let letters = Letters { fold_case: true, ..Letters::default() };
+        assert_eq!(0, close_matches(&ids, Metric::Levenshtein, 0, &letters)[0].distance);
+    }
+}
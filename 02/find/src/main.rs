@@ -1,53 +1,129 @@
-use std::fs;
+extern crate aoc_common;
+extern crate find;
+extern crate ims;
+
+use find::distance::Metric;
+use ims::letters::Letters;
+use std::env;
+use std::process;
+
+const USAGE: &str = "\
+Usage: find [--all | --within <distance> [--metric hamming|levenshtein]] [--graphemes] [--fold-case] [--normalise nfc|nfkc] [input]
+
+Prints the letters shared by the two box IDs that differ by a single letter.
+--all prints every such pair instead, one per line: the two IDs, which
+letter differs counting from 0, and the letters they share.
+
+--within prints every pair of IDs no further apart than the distance, one
+per line: the two IDs, how far apart they are, and the letters they share
+once lined up. Distances are Hamming distances unless a --metric is given.
+
+Letters are compared a char at a time. --graphemes treats a letter and the
+marks combined with it as one, --fold-case ignores case, and --normalise
+first brings letters written in different ways to the same form.";
 
 fn main() {
-    let input = read_input_lines();
-    let result = process_lines(input);
-    println!("Result: {}\n", result);
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    let input = aoc_common::read_input_from(options.input.as_deref());
+
+    if options.all {
+        let box_ids: Vec<&str> = aoc_common::lines(&input).collect();
+        for near_match in find::near_matches(&box_ids, &options.letters) {
+            println!("{} {} {} {}", box_ids[near_match.first], box_ids[near_match.second], near_match.position, near_match.common);
+        }
+        return;
+    }
+
+    if let Some(within) = options.within {
+        let box_ids: Vec<&str> = aoc_common::lines(&input).collect();
+        for close_match in find::close_matches(&box_ids, options.metric, within, &options.letters) {
+            println!("{} {} {} {}", box_ids[close_match.first], box_ids[close_match.second], close_match.distance, close_match.common);
+        }
+        return;
+    }
+
+    let result = find::common_letters(&input, &options.letters);
+    aoc_common::print_result(result);
 }
 
-fn read_input_lines() -> String {
-    let input_filename = String::from("input.txt");
-    fs::read_to_string(input_filename)
-        .expect("Failed to read file")
+#[derive(Debug, PartialEq)]
+struct Options {
+    all: bool,
+    within: Option<usize>,
+    metric: Metric,
+    letters: Letters,
+    input: Option<String>,
 }
 
-fn process_lines(input: String) -> String {
-    let mut box_ids: Vec<&str> = input.lines()
-        .map(|id| {id.trim()})
-        .filter(|id| {id.len() > 0})
-        .collect();
-
-    while let Some(id) = box_ids.pop() {
-        for other_id in box_ids.iter().cloned() {
-            let matching = matching_chars(id, other_id);
-            if matching.len() == (id.len() - 1) {
-                return matching;
-            }
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { all: false, within: None, metric: Metric::Hamming, letters: Letters::default(), input: None };
+    let mut metric = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => options.all = true,
+            "--within" => {
+                let value = option_value(arg, args.next())?;
+                let within = value.parse()
+                    .map_err(|_| format!("Expected a distance for --within, got '{}'", value))?;
+                options.within = Some(within);
+            },
+            "--metric" => metric = Some(option_value(arg, args.next())?.parse()?),
+            _ if options.letters.parse_option(arg, &mut args)? => {},
+            _ if options.input.is_none() => options.input = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    if options.all && options.within.is_some() {
+        return Err(String::from("--all and --within can't be used together"));
+    }
+    if let Some(metric) = metric {
+        if options.within.is_none() {
+            return Err(String::from("--metric is only for --within"));
         }
+        options.metric = metric;
     }
-    return String::new();
+
+    Ok(options)
 }
 
-fn matching_chars(id: &str, other_id: &str) -> String {
-    id.chars().zip(other_id.chars())
-        .filter_map(|(a, b)| {
-            if a == b {
-                Some(a)
-            } else {
-                None
-            }
-        }).collect()
+fn option_value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", option))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
     #[test]
-    fn test_example() {
-        let input = String::from("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n");
-        let result = process_lines(input);
-        assert_eq!("fgij", result);
+    fn test_parse_args() {
+        let options = parse_args(&args(&[])).unwrap();
+        assert_eq!(Options { all: false, within: None, metric: Metric::Hamming, letters: Letters::default(), input: None }, options);
+
+        let options = parse_args(&args(&["-", "--all"])).unwrap();
+        assert_eq!(Options { all: true, within: None, metric: Metric::Hamming, letters: Letters::default(), input: Some(String::from("-")) }, options);
+
+        let options = parse_args(&args(&["--metric", "levenshtein", "--within", "3", "--graphemes"])).unwrap();
+        let letters = Letters { graphemes: true, ..Letters::default() };
+        assert_eq!(Options { all: false, within: Some(3), metric: Metric::Levenshtein, letters, input: None }, options);
+
+        assert!(parse_args(&args(&["a.txt", "b.txt"])).is_err());
+        assert!(parse_args(&args(&["--within", "two"])).is_err());
+        assert!(parse_args(&args(&["--within", "2", "--metric", "euclid"])).is_err());
+        assert!(parse_args(&args(&["--metric", "hamming"])).is_err());
+        assert!(parse_args(&args(&["--all", "--within", "1"])).is_err());
     }
 }
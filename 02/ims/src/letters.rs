@@ -0,0 +1,131 @@
+//! What counts as a letter of a box ID, for inventories that aren't plain
+//! ASCII.
+
+use caseless;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A Unicode normalisation form, so that letters written with different
+/// code points can count as the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalisation {
+    /// Canonical composition, which joins letters to their accents where it
+    /// can.
+    Nfc,
+    /// Compatibility composition, which also turns ligatures, full width
+    /// letters and the like into their plain forms.
+    Nfkc,
+}
+
+impl FromStr for Normalisation {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Normalisation, String> {
+        match string {
+            "nfc" => Ok(Normalisation::Nfc),
+            "nfkc" => Ok(Normalisation::Nfkc),
+            other => Err(format!("Unknown normalisation '{}', expected nfc or nfkc", other)),
+        }
+    }
+}
+
+/// How to split a box ID into letters. By default every `char` is a letter
+/// of its own, as the puzzle expects.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Letters {
+    /// Whether a letter is an extended grapheme cluster, so that a letter
+    /// and the marks combined with it count as one.
+    pub graphemes: bool,
+    /// Whether letters differing only in case are the same.
+    pub fold_case: bool,
+    pub normalisation: Option<Normalisation>,
+}
+
+impl Letters {
+    /// Splits a box ID into its letters, case folded and normalised first
+    /// if asked for.
+    pub fn split(&self, id: &str) -> Vec<String> {
+        let mut id = id.to_string();
+        if self.fold_case {
+            id = caseless::default_case_fold_str(&id);
+        }
+        match self.normalisation {
+            Some(Normalisation::Nfc) => id = id.nfc().collect(),
+            Some(Normalisation::Nfkc) => id = id.nfkc().collect(),
+            None => {},
+        }
+
+        if self.graphemes {
+            id.graphemes(true).map(|letter| letter.to_string()).collect()
+        } else {
+            id.chars().map(|letter| letter.to_string()).collect()
+        }
+    }
+
+    /// Takes an option from a command line if it is one of the options for
+    /// how to split box IDs into letters, with its value from `args` if it
+    /// has one. Gives whether the option was taken.
+    pub fn parse_option<'a, I>(&mut self, option: &str, args: &mut I) -> Result<bool, String>
+        where I: Iterator<Item = &'a String>
+    {
+        match option {
+            "--graphemes" => self.graphemes = true,
+            "--fold-case" => self.fold_case = true,
+            "--normalise" => {
+                let value = args.next().ok_or_else(|| format!("Missing value for {}", option))?;
+                self.normalisation = Some(value.parse()?);
+            },
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_chars() {
+        assert_eq!(vec!["a", "b", "c"], Letters::default().split("abc"));
+
+        // An e followed by a combining acute accent is two chars
+        assert_eq!(vec!["e", "\u{301}", "x"], Letters::default().split("e\u{301}x"));
+    }
+
+    #[test]
+    fn test_split_graphemes() {
+        let letters = Letters { graphemes: true, ..Letters::default() };
+        assert_eq!(vec!["e\u{301}", "x"], letters.split("e\u{301}x"));
+        assert_eq!(vec!["🇬🇧", "a"], letters.split("🇬🇧a"));
+    }
+
+    #[test]
+    fn test_split_normalised() {
+        let letters = Letters { normalisation: Some(Normalisation::Nfc), ..Letters::default() };
+        assert_eq!(vec!["\u{e9}", "x"], letters.split("e\u{301}x"));
+
+        let letters = Letters { normalisation: Some(Normalisation::Nfkc), ..Letters::default() };
+        assert_eq!(vec!["f", "i", "A"], letters.split("\u{fb01}\u{ff21}"));
+    }
+
+    #[test]
+    fn test_split_folded() {
+        let letters = Letters { fold_case: true, ..Letters::default() };
+        assert_eq!(vec!["s", "s", "a", "b"], letters.split("ßAb"));
+    }
+
+    #[test]
+    fn test_parse_option() {
+        let values = [String::from("nfkc")];
+        let mut letters = Letters::default();
+        assert_eq!(Ok(true), letters.parse_option("--graphemes", &mut values.iter()));
+        assert_eq!(Ok(true), letters.parse_option("--normalise", &mut values.iter()));
+        assert_eq!(Ok(false), letters.parse_option("--histogram", &mut values.iter()));
+        assert_eq!(Letters { graphemes: true, fold_case: false, normalisation: Some(Normalisation::Nfkc) }, letters);
+
+        assert!(letters.parse_option("--normalise", &mut Vec::new().iter()).is_err());
+        assert!(letters.parse_option("--normalise", &mut [String::from("nfd")].iter()).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+#![allow(clippy::needless_return)]
+
+extern crate aoc_common;
+extern crate caseless;
+extern crate unicode_normalization;
+extern crate unicode_segmentation;
+
+pub mod letters;
+
+use aoc_common::{Answer, Error, Solution};
+use letters::Letters;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::TryFrom;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process_lines(input.to_string())?.into())
+    }
+}
+
+/// The multiplicities the puzzle's checksum is made from: letters that
+/// appear exactly twice, and exactly three times.
+pub const PUZZLE_MULTIPLICITIES: [usize; 2] = [2, 3];
+
+pub fn process_lines(input: String) -> Result<i64, Error> {
+    histogram(&input, &Letters::default()).checksum(&PUZZLE_MULTIPLICITIES)
+}
+
+/// For every multiplicity, how many box IDs have a letter that appears
+/// exactly that many times. An ID counts once towards each multiplicity
+/// however many of its letters share it.
+#[derive(Debug, Default, PartialEq)]
+pub struct Histogram {
+    /// How many box IDs went into the histogram.
+    pub ids: usize,
+    pub counts: BTreeMap<usize, usize>,
+}
+
+impl Histogram {
+    /// Adds a box ID to the histogram.
+    pub fn add(&mut self, id: &str, letters: &Letters) {
+        self.ids += 1;
+        for multiplicity in multiplicities(id, letters) {
+            *self.counts.entry(multiplicity).or_insert(0) += 1;
+        }
+    }
+
+    /// How many box IDs have a letter appearing exactly `multiplicity`
+    /// times.
+    pub fn ids_with(&self, multiplicity: usize) -> usize {
+        self.counts.get(&multiplicity).cloned().unwrap_or(0)
+    }
+
+    /// Multiplies together how many box IDs have each of `multiplicities`.
+    pub fn checksum(&self, multiplicities: &[usize]) -> Result<i64, Error> {
+        let mut checksum: i64 = 1;
+        for multiplicity in multiplicities {
+            checksum = i64::try_from(self.ids_with(*multiplicity)).ok()
+                .and_then(|ids| checksum.checked_mul(ids))
+                .ok_or_else(|| Error::Invalid(String::from("the checksum is too big for a 64 bit integer")))?;
+        }
+        return Ok(checksum);
+    }
+
+    /// The histogram as CSV, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("multiplicity,ids\n");
+        for (multiplicity, ids) in &self.counts {
+            csv += &format!("{},{}\n", multiplicity, ids);
+        }
+        return csv;
+    }
+}
+
+/// Builds the histogram of letter multiplicities for every box ID in the
+/// input.
+pub fn histogram(input: &str, letters: &Letters) -> Histogram {
+    let mut histogram = Histogram::default();
+    for line in aoc_common::lines(input) {
+        histogram.add(line, letters);
+    }
+    return histogram;
+}
+
+/// How many times the letters of a box ID appear, each count given once.
+pub fn multiplicities(id: &str, letters: &Letters) -> BTreeSet<usize> {
+    count_letters(id, letters).values().cloned().collect()
+}
+
+fn count_letters(line: &str, letters: &Letters) -> HashMap<String, usize> {
+    let mut letter_counts = HashMap::new();
+    for letter in letters.split(line) {
+        letter_counts.entry(letter)
+            .and_modify(|e| { *e += 1 })
+            .or_insert(1);
+    }
+    letter_counts
+}
+
+/// Reads a comma separated list of multiplicities, like `2,3`.
+pub fn parse_multiplicities(text: &str) -> Result<Vec<usize>, String> {
+    text.split(',')
+        .map(|multiplicity| match multiplicity.trim().parse() {
+            Ok(multiplicity) if multiplicity > 0 => Ok(multiplicity),
+            _ => Err(format!("Expected a multiplicity of at least 1, got '{}'", multiplicity)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use letters::Normalisation;
+
+    #[test]
+    fn test_example() {
+        let input = String::from("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n");
+        let result = process_lines(input);
+        assert_eq!(Ok(12), result);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = histogram("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n", &Letters::default());
+        assert_eq!(7, histogram.ids);
+        assert_eq!(6, histogram.ids_with(1));
+        assert_eq!(4, histogram.ids_with(2));
+        assert_eq!(3, histogram.ids_with(3));
+        assert_eq!(0, histogram.ids_with(4));
+        assert_eq!("multiplicity,ids\n1,6\n2,4\n3,3\n", histogram.to_csv());
+
+        assert_eq!(Ok(72), histogram.checksum(&[1, 2, 3]));
+        assert_eq!(Ok(0), histogram.checksum(&[2, 4]));
+        assert_eq!(Ok(1), histogram.checksum(&[]));
+    }
+
+    #[test]
+    fn test_multiplicities() {
+        let expected: BTreeSet<usize> = [1, 2, 3].iter().cloned().collect();
+        assert_eq!(expected, multiplicities("abbccc", &Letters::default()));
+        assert!(multiplicities("", &Letters::default()).is_empty());
+    }
+
+    #[test]
+    fn test_multiplicities_unicode() {
+        // Two e's with acute accents, one composed and one not, and an
+        // upper case E
+        let id = "\u{e9}e\u{301}Ea";
+        let as_chars: BTreeSet<usize> = [1].iter().cloned().collect();
+        assert_eq!(as_chars, multiplicities(id, &Letters::default()));
+
+        let letters = Letters { graphemes: true, normalisation: Some(Normalisation::Nfc), ..Letters::default() };
+        let expected: BTreeSet<usize> = [1, 2].iter().cloned().collect();
+        assert_eq!(expected, multiplicities(id, &letters));
+
+        // The accents are counted twice on their own, unless each goes with
+        // its e and the case of the e's is ignored
+        let id = "E\u{301}e\u{301}";
+        let as_chars: BTreeSet<usize> = [1, 2].iter().cloned().collect();
+        assert_eq!(as_chars, multiplicities(id, &Letters::default()));
+
+        let letters = Letters { graphemes: true, fold_case: true, ..Letters::default() };
+        let expected: BTreeSet<usize> = [2].iter().cloned().collect();
+        assert_eq!(expected, multiplicities(id, &letters));
+    }
+
+    #[test]
+    fn test_parse_multiplicities() {
+        assert_eq!(Ok(vec![2, 3]), parse_multiplicities("2,3"));
+        assert_eq!(Ok(vec![4]), parse_multiplicities("4"));
+        assert!(parse_multiplicities("0").is_err());
+        assert!(parse_multiplicities("2,,3").is_err());
+    }
+}
@@ -1,75 +1,99 @@
-use std::fs;
-use std::collections::HashMap;
+extern crate aoc_common;
+extern crate ims;
 
-fn main() {
-    let input = read_input_lines();
-    let result = process_lines(input);
-    println!("Result: {}\n", result);
-}
+use ims::letters::Letters;
+use std::env;
+use std::process;
 
-fn read_input_lines() -> String {
-    let input_filename = String::from("input.txt");
-    fs::read_to_string(input_filename)
-        .expect("Failed to read file")
-}
+const USAGE: &str = "\
+Usage: ims [--multiplicities <list>] [--histogram] [--graphemes] [--fold-case] [--normalise nfc|nfkc] [input]
 
-fn process_lines(input: String) -> i32 {
-    let mut num_pairs = 0;
-    let mut num_triplets = 0;
+The checksum multiplies together how many box IDs have a letter appearing
+exactly so many times, for each of a comma separated list of multiplicities.
+The puzzle's is 2,3. --histogram prints how many IDs have each multiplicity
+as CSV in place of the checksum.
 
-    for line in input.lines() {
-        // Cleanup the input line
-        let line = line.trim();
-        if line.len() == 0 {
-            continue
-        }
+Letters are counted a char at a time. --graphemes counts a letter and the
+marks combined with it as one, --fold-case counts upper and lower case as
+the same, and --normalise first brings letters written in different ways to
+the same form.";
 
-        let letter_counts = count_letters(line);
-        let (pairs_adjustment, triplets_adjustment) = score_pairs_and_triplets(letter_counts);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    let input = aoc_common::read_input_from(options.input.as_deref());
+    let histogram = ims::histogram(&input, &options.letters);
 
-        // Update the counts
-        num_pairs += pairs_adjustment;
-        num_triplets += triplets_adjustment;
+    if options.histogram {
+        print!("{}", histogram.to_csv());
+        return;
     }
 
-    checksum(num_pairs, num_triplets)
+    let result = aoc_common::unwrap_or_exit(histogram.checksum(&options.multiplicities));
+    aoc_common::print_result(result);
 }
 
-fn score_pairs_and_triplets(letter_counts: HashMap<char, i32>) -> (i32, i32) {
-    let mut pairs_adjustment = 0;
-    let mut triplets_adjustment = 0;
-    for (_, count) in letter_counts {
-        match count {
-            2 => pairs_adjustment = 1,
-            3 => triplets_adjustment = 1,
-            _ => {}
-        }
-    }
-    (pairs_adjustment, triplets_adjustment)
+#[derive(Debug, PartialEq)]
+struct Options {
+    multiplicities: Vec<usize>,
+    histogram: bool,
+    letters: Letters,
+    input: Option<String>,
 }
 
-fn count_letters(line: &str) -> HashMap<char, i32> {
-    let mut letter_counts = HashMap::new();
-    for letter in line.chars() {
-        letter_counts.entry(letter)
-            .and_modify(|e| { *e += 1 })
-            .or_insert(1);
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        multiplicities: ims::PUZZLE_MULTIPLICITIES.to_vec(),
+        histogram: false,
+        letters: Letters::default(),
+        input: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--multiplicities" => {
+                let value = args.next().ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.multiplicities = ims::parse_multiplicities(value)?;
+            },
+            "--histogram" => options.histogram = true,
+            _ if options.letters.parse_option(arg, &mut args)? => {},
+            _ if options.input.is_none() => options.input = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
     }
-    letter_counts
-}
 
-fn checksum(num_pairs: i32, num_triplets: i32) -> i32 {
-    num_pairs * num_triplets
+    Ok(options)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
     #[test]
-    fn test_example() {
-        let input = String::from("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n");
-        let result = process_lines(input);
-        assert_eq!(12, result);
+    fn test_parse_args() {
+        let options = parse_args(&args(&[])).unwrap();
+        assert_eq!(Options { multiplicities: vec![2, 3], histogram: false, letters: Letters::default(), input: None }, options);
+
+        let options = parse_args(&args(&["--multiplicities", "2,3,4", "-"])).unwrap();
+        assert_eq!(Options { multiplicities: vec![2, 3, 4], histogram: false, letters: Letters::default(), input: Some(String::from("-")) }, options);
+
+        let options = parse_args(&args(&["--histogram", "--fold-case"])).unwrap();
+        assert!(options.histogram);
+        assert!(options.letters.fold_case);
+
+        assert!(parse_args(&args(&["--multiplicities"])).is_err());
+        assert!(parse_args(&args(&["--multiplicities", "two"])).is_err());
+        assert!(parse_args(&args(&["a.txt", "b.txt"])).is_err());
     }
 }
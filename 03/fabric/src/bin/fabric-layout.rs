@@ -0,0 +1,156 @@
+use fabric::{layout, render};
+use std::env;
+use std::fs;
+use std::process;
+
+const USAGE: &str = "\
+Usage: fabric-layout [--rearrange <width>x<height> [--seed <n>]] [--heatmap <png>] [--outline] [--labels <svg>] [input]
+
+Rearranges or draws day 3's claims, and needs at least one of --rearrange,
+--heatmap and --labels.
+
+--rearrange moves claims around a sheet of the size given until none
+overlap, leaving as many as it can where they are, and prints where every
+claim ends up instead. Different seeds may find better layouts.
+
+--heatmap draws a PNG a pixel to the square inch, coloured by how many
+claims want each: black for none, blue for one, and yellow through red for
+more. --outline outlines the claims that overlap nothing in green.
+
+--labels writes an SVG outlining every claim and labelling it with its id,
+over the heatmap if there is one. Both draw the new layout when rearranging.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    let input = aoc_common::read_input_from(options.input.as_deref());
+
+    let mut claims = aoc_common::unwrap_or_exit(overlaps::claims_from(&input));
+
+    if let Some((width, height)) = options.rearrange {
+        let layout = aoc_common::unwrap_or_exit(layout::rearrange(&claims, width, height, options.seed));
+        eprintln!("Moved {} of {} claims", layout.moved.len(), claims.len());
+        claims = layout.claims;
+    }
+
+    if let Some(path) = &options.heatmap {
+        let image = aoc_common::unwrap_or_exit(render::heatmap(&claims, options.outline));
+        if let Err(error) = image.save(path) {
+            eprintln!("Failed to write {}: {}", path, error);
+            process::exit(1);
+        }
+    }
+
+    if let Some(path) = &options.labels {
+        let svg = aoc_common::unwrap_or_exit(render::labels(&claims, options.heatmap.as_deref()));
+        if let Err(error) = fs::write(path, svg) {
+            eprintln!("Failed to write {}: {}", path, error);
+            process::exit(1);
+        }
+    }
+
+    if options.rearrange.is_some() {
+        for claim in &claims {
+            println!("{}", claim);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    rearrange: Option<(i64, i64)>,
+    seed: u64,
+    heatmap: Option<String>,
+    outline: bool,
+    labels: Option<String>,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { rearrange: None, seed: 0, heatmap: None, outline: false, labels: None, input: None };
+    let mut seed = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rearrange" => {
+                let value = option_value(arg, args.next())?;
+                options.rearrange = Some(parse_size(value).ok_or_else(|| format!("Expected a sheet size like 1000x1000, got '{}'", value))?);
+            },
+            "--seed" => {
+                let value = option_value(arg, args.next())?;
+                seed = Some(value.parse().map_err(|_| format!("Expected a number for --seed, got '{}'", value))?);
+            },
+            "--heatmap" => options.heatmap = Some(option_value(arg, args.next())?.clone()),
+            "--outline" => options.outline = true,
+            "--labels" => options.labels = Some(option_value(arg, args.next())?.clone()),
+            _ if options.input.is_none() && !arg.starts_with("--") => options.input = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    if options.rearrange.is_none() && options.heatmap.is_none() && options.labels.is_none() {
+        return Err(String::from("Nothing to do without --rearrange, --heatmap or --labels"));
+    }
+    if options.outline && options.heatmap.is_none() {
+        return Err(String::from("--outline is only for --heatmap"));
+    }
+    if let Some(seed) = seed {
+        if options.rearrange.is_none() {
+            return Err(String::from("--seed is only for --rearrange"));
+        }
+        options.seed = seed;
+    }
+
+    Ok(options)
+}
+
+fn parse_size(text: &str) -> Option<(i64, i64)> {
+    let cross = text.find('x')?;
+    Some((text[..cross].parse().ok()?, text[(cross + 1)..].parse().ok()?))
+}
+
+fn option_value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", option))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&["--heatmap", "fabric.png", "--outline", "--labels", "fabric.svg", "-"])).unwrap();
+        let expected = Options {
+            rearrange: None,
+            seed: 0,
+            heatmap: Some(String::from("fabric.png")),
+            outline: true,
+            labels: Some(String::from("fabric.svg")),
+            input: Some(String::from("-")),
+        };
+        assert_eq!(expected, options);
+
+        let options = parse_args(&args(&["--rearrange", "1000x800", "--seed", "7"])).unwrap();
+        assert_eq!(Some((1000, 800)), options.rearrange);
+        assert_eq!(7, options.seed);
+
+        assert!(parse_args(&args(&["--heatmap"])).is_err());
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["--boxes", "--heatmap", "boxes.png"])).is_err());
+        assert!(parse_args(&args(&["--rearrange", "1000"])).is_err());
+        assert!(parse_args(&args(&["--seed", "7"])).is_err());
+        assert!(parse_args(&args(&["--outline"])).is_err());
+        assert!(parse_args(&args(&["--labels", "a.svg", "a.txt", "b.txt"])).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Moving claims around a sheet of fabric until none of them overlap.
+
+use aoc_common::Error;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use overlaps::sweep;
+use overlaps::Claim;
+
+/// How many differently shuffled tries to make at a layout, keeping the one
+/// that moves the fewest claims.
+pub const ATTEMPTS: usize = 8;
+
+/// Claims placed on a sheet without overlapping, and the ids of the claims
+/// that had to move from where they asked to be, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub claims: Vec<Claim>,
+    pub moved: Vec<i32>,
+}
+
+/// Finds somewhere on a `width` by `height` sheet for every claim, leaving
+/// as many as it can where they asked to be, or says why there's nowhere.
+///
+/// The claims left alone are found by dropping whichever claim overlaps the
+/// most others until none overlap, and the rest are packed into the space
+/// left, each as far up and then as far left as it goes, biggest first.
+/// When a claim won't fit anywhere, the biggest claim left alone where it
+/// wanted to be is moved too, and the packing starts again. None of this is
+/// sure to be the best, so the same `seed` always gives the
+/// same layout, but another seed may move fewer claims or find a layout
+/// where this one didn't.
+pub fn rearrange(claims: &[Claim], width: i64, height: i64, seed: u64) -> Result<Layout, Error> {
+    check_possible(claims, width, height)?;
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut best: Option<Layout> = None;
+    for _ in 0..ATTEMPTS {
+        let priorities: Vec<u64> = claims.iter().map(|_| rng.gen()).collect();
+        let mut staying = claims_staying(claims, width, height, &priorities);
+        let layout = loop {
+            match pack(claims, width, height, &staying, &priorities) {
+                Ok(layout) => break Some(layout),
+                Err(stuck) => match in_the_way(claims, &staying, stuck, &priorities) {
+                    Some(index) => staying[index] = false,
+                    None => break None,
+                },
+            }
+        };
+
+        if let Some(layout) = layout {
+            if best.as_ref().is_none_or(|best| layout.moved.len() < best.moved.len()) {
+                best = Some(layout);
+            }
+        }
+    }
+
+    best.ok_or_else(|| Error::Invalid(format!("couldn't find a layout for the claims on a {}x{} sheet in {} attempts", width, height, ATTEMPTS)))
+}
+
+/// Turns away claims that can't all fit whatever is done with them.
+fn check_possible(claims: &[Claim], width: i64, height: i64) -> Result<(), Error> {
+    let no_layout = |reason: String| Err(Error::Invalid(format!("no layout exists: {}", reason)));
+
+    if width < 0 || height < 0 {
+        return no_layout(format!("a sheet can't be {}x{}", width, height));
+    }
+    for claim in claims {
+        if claim.width < 0 || claim.height < 0 {
+            return no_layout(format!("claim #{} can't be {}x{}", claim.claim_id, claim.width, claim.height));
+        }
+        if claim.width > width || claim.height > height {
+            return no_layout(format!("claim #{} is {}x{}, bigger than the sheet", claim.claim_id, claim.width, claim.height));
+        }
+    }
+
+    let area: i128 = claims.iter().map(|claim| claim.area()).sum();
+    let sheet = width as i128 * height as i128;
+    if area > sheet {
+        return no_layout(format!("the claims cover {} square inches, more than the sheet's {}", area, sheet));
+    }
+    Ok(())
+}
+
+/// Which claims can stay where they are: those on the sheet, less the most
+/// overlapping of them one at a time until none overlap. Ties go to the
+/// claim with the highest priority.
+fn claims_staying(claims: &[Claim], width: i64, height: i64, priorities: &[u64]) -> Vec<bool> {
+    let mut staying: Vec<bool> = claims.iter()
+        .map(|claim| claim.x >= 0 && claim.y >= 0 && claim.right <= width && claim.bottom <= height)
+        .collect();
+
+    let on_sheet: Vec<usize> = (0..claims.len()).filter(|index| staying[*index]).collect();
+    let placed: Vec<Claim> = on_sheet.iter().map(|index| claims[*index].clone()).collect();
+    let mut overlaps = vec![Vec::new(); claims.len()];
+    for (first, second) in sweep::overlapping_pairs(&placed) {
+        overlaps[on_sheet[first]].push(on_sheet[second]);
+        overlaps[on_sheet[second]].push(on_sheet[first]);
+    }
+
+    let mut conflicts: Vec<usize> = overlaps.iter().map(|others| others.len()).collect();
+    let mut most = BinaryHeap::new();
+    for index in on_sheet {
+        if conflicts[index] > 0 {
+            most.push((conflicts[index], priorities[index], index));
+        }
+    }
+
+    // Counts change as claims go, so stale entries are skipped
+    while let Some((count, _, index)) = most.pop() {
+        if !staying[index] || count != conflicts[index] || count == 0 {
+            continue;
+        }
+
+        staying[index] = false;
+        for other in &overlaps[index] {
+            if staying[*other] {
+                conflicts[*other] -= 1;
+                if conflicts[*other] > 0 {
+                    most.push((conflicts[*other], priorities[*other], *other));
+                }
+            }
+        }
+    }
+
+    staying
+}
+
+/// Claims already placed on the sheet, filed by the square blocks of the
+/// sheet they cover so that checking for overlaps only looks at claims
+/// nearby.
+struct Placed {
+    claims: Vec<Claim>,
+    block: i64,
+    blocks: HashMap<(i64, i64), Vec<usize>>,
+    /// Where claims might be placed from top to bottom: the top of the
+    /// sheet and the bottom of every claim placed.
+    rows: BTreeSet<i64>,
+}
+
+impl Placed {
+    fn new(block: i64) -> Placed {
+        let mut rows = BTreeSet::new();
+        rows.insert(0);
+        Placed { claims: Vec::new(), block, blocks: HashMap::new(), rows }
+    }
+
+    /// The blocks `claim` covers, as the blocks across and down it starts
+    /// and ends in.
+    fn blocks_under(&self, claim: &Claim) -> (i64, i64, i64, i64) {
+        let left = claim.x.div_euclid(self.block);
+        let top = claim.y.div_euclid(self.block);
+        let right = (claim.right - 1).div_euclid(self.block);
+        let bottom = (claim.bottom - 1).div_euclid(self.block);
+        (left, top, right, bottom)
+    }
+
+    fn add(&mut self, claim: Claim) {
+        if claim.area() > 0 {
+            let (left, top, right, bottom) = self.blocks_under(&claim);
+            for y in top..=bottom {
+                for x in left..=right {
+                    self.blocks.entry((x, y)).or_default().push(self.claims.len());
+                }
+            }
+            self.rows.insert(claim.bottom);
+        }
+        self.claims.push(claim);
+    }
+
+    /// A placed claim `claim` would overlap, if any.
+    fn overlapping(&self, claim: &Claim) -> Option<&Claim> {
+        if claim.area() == 0 {
+            return None;
+        }
+        let (left, top, right, bottom) = self.blocks_under(claim);
+        for y in top..=bottom {
+            for x in left..=right {
+                for index in self.blocks.get(&(x, y)).into_iter().flatten() {
+                    let other = &self.claims[*index];
+                    if claim.x < other.right && other.x < claim.right && claim.y < other.bottom && other.y < claim.bottom {
+                        return Some(other);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The claim staying where it is to move next when the claim at `stuck`
+/// doesn't fit: the biggest in the way of where it asked to be, or the
+/// biggest of all if nothing's in the way. Ties go to the highest priority.
+fn in_the_way(claims: &[Claim], staying: &[bool], stuck: usize, priorities: &[u64]) -> Option<usize> {
+    let staying: Vec<usize> = (0..claims.len()).filter(|index| staying[*index]).collect();
+    let biggest = |candidates: &mut dyn Iterator<Item = &usize>| candidates
+        .max_by_key(|index| (claims[**index].area(), priorities[**index]))
+        .cloned();
+
+    let wanted = &claims[stuck];
+    biggest(&mut staying.iter().filter(|index| wanted.intersection(&claims[**index]).is_some()))
+        .or_else(|| biggest(&mut staying.iter()))
+}
+
+/// Places the claims that aren't staying where they are in the space the
+/// others leave, biggest first, with ties going to the highest priority.
+/// Gives the claim that didn't fit if one doesn't.
+fn pack(claims: &[Claim], width: i64, height: i64, staying: &[bool], priorities: &[u64]) -> Result<Layout, usize> {
+    let block = claims.iter().map(|claim| claim.width.max(claim.height)).max().unwrap_or(1).max(1);
+    let mut placed = Placed::new(block);
+    let mut layout = vec![None; claims.len()];
+    for (index, claim) in claims.iter().enumerate() {
+        if staying[index] {
+            placed.add(claim.clone());
+            layout[index] = Some(claim.clone());
+        }
+    }
+
+    let mut moving: Vec<usize> = (0..claims.len()).filter(|index| !staying[*index]).collect();
+    moving.sort_by_key(|index| (Reverse(claims[*index].area()), Reverse(priorities[*index])));
+
+    // Where claims of each size were put. Nothing above that row fitted
+    // them, so nothing above it fits claims at least as wide and as tall.
+    let mut found_at: HashMap<(i64, i64), i64> = HashMap::new();
+    let mut moved = Vec::new();
+    for index in moving {
+        let claim = &claims[index];
+        let from = found_at.iter()
+            .filter(|((found_width, found_height), _)| *found_width <= claim.width && *found_height <= claim.height)
+            .map(|(_, row)| *row)
+            .max()
+            .unwrap_or(0);
+        let spot = find_spot(&placed, claim, width, height, from).ok_or(index)?;
+        found_at.insert((claim.width, claim.height), spot.y);
+        placed.add(spot.clone());
+        layout[index] = Some(spot);
+        moved.push(claim.claim_id);
+    }
+
+    moved.sort_unstable();
+    Ok(Layout { claims: layout.into_iter().map(|claim| claim.unwrap()).collect(), moved })
+}
+
+/// The highest and then leftmost place `claim` fits without overlapping
+/// anything. A claim can always go as far up as the top of the sheet or the
+/// bottom of another claim, so only those rows are tried, and along each a
+/// claim in the way means trying again past its right edge. Rows above
+/// `from` are skipped.
+fn find_spot(placed: &Placed, claim: &Claim, width: i64, height: i64, from: i64) -> Option<Claim> {
+    for y in placed.rows.range(from..).cloned() {
+        if y as i128 + claim.height as i128 > height as i128 {
+            break;
+        }
+
+        let mut x = 0;
+        while x as i128 + claim.width as i128 <= width as i128 {
+            let spot = Claim::new(claim.claim_id, x, y, claim.width, claim.height).unwrap();
+            match placed.overlapping(&spot) {
+                Some(other) => x = other.right,
+                None => return Some(spot),
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use overlaps::claims_from;
+
+    fn check(claims: &[Claim], layout: &Layout, width: i64, height: i64) {
+        assert_eq!(claims.len(), layout.claims.len());
+        assert!(sweep::overlapping_pairs(&layout.claims).is_empty());
+        for (claim, placed) in claims.iter().zip(layout.claims.iter()) {
+            assert_eq!((claim.claim_id, claim.width, claim.height), (placed.claim_id, placed.width, placed.height));
+            assert!(placed.x >= 0 && placed.y >= 0 && placed.right <= width && placed.bottom <= height);
+            assert_eq!(claim != placed, layout.moved.contains(&claim.claim_id));
+        }
+    }
+
+    #[test]
+    fn test_example() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+        let layout = rearrange(&claims, 8, 8, 1).unwrap();
+        check(&claims, &layout, 8, 8);
+
+        // Neither of the overlapping claims fits around the other, so both
+        // have to move
+        assert_eq!(2, layout.moved.len());
+        assert!(!layout.moved.contains(&3));
+
+        // With more room only one does
+        let layout = rearrange(&claims, 12, 12, 1).unwrap();
+        check(&claims, &layout, 12, 12);
+        assert_eq!(1, layout.moved.len());
+    }
+
+    #[test]
+    fn test_nothing_to_move() {
+        let claims = claims_from("#1 @ 0,0: 2x2\n#2 @ 2,0: 2x2\n").unwrap();
+        let layout = rearrange(&claims, 4, 2, 1).unwrap();
+        assert_eq!(claims, layout.claims);
+        assert!(layout.moved.is_empty());
+    }
+
+    #[test]
+    fn test_claims_off_the_sheet_move() {
+        let claims = claims_from("#1 @ 0,0: 2x2\n#2 @ -1,5: 2x2\n#3 @ 3,3: 2x2\n").unwrap();
+        let layout = rearrange(&claims, 4, 4, 1).unwrap();
+        check(&claims, &layout, 4, 4);
+        assert_eq!(vec![2, 3], layout.moved);
+    }
+
+    #[test]
+    fn test_tight_fit() {
+        // Four claims piled on each other that only fit by tiling the sheet
+        let claims = claims_from("#1 @ 0,0: 3x2\n#2 @ 0,0: 3x2\n#3 @ 0,0: 2x3\n#4 @ 0,0: 2x3\n#5 @ 0,0: 1x1\n").unwrap();
+        let layout = rearrange(&claims, 5, 5, 7).unwrap();
+        check(&claims, &layout, 5, 5);
+        assert_eq!(4, layout.moved.len());
+    }
+
+    #[test]
+    fn test_same_seed_same_layout() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 2,2: 3x3\n#5 @ 4,4: 3x3\n").unwrap();
+        assert_eq!(rearrange(&claims, 10, 10, 42).unwrap(), rearrange(&claims, 10, 10, 42).unwrap());
+    }
+
+    #[test]
+    fn test_huge_sheet() {
+        // Trying past the first claim goes beyond i64::MAX across
+        let claims = vec![
+            Claim::new(1, 0, 0, i64::MAX - 2, 1).unwrap(),
+            Claim::new(2, 0, -5, 3, 1).unwrap(),
+        ];
+        let layout = rearrange(&claims, i64::MAX, 2, 1).unwrap();
+        check(&claims, &layout, i64::MAX, 2);
+        assert_eq!(vec![2], layout.moved);
+
+        // And below it beyond i64::MAX down, where nothing fits
+        let claims = vec![
+            Claim::new(1, 0, 0, 1, i64::MAX - 2).unwrap(),
+            Claim::new(2, -5, 0, 2, 3).unwrap(),
+        ];
+        assert!(rearrange(&claims, 2, i64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn test_no_layout() {
+        let claims = claims_from("#1 @ 0,0: 3x3\n#2 @ 0,0: 3x3\n").unwrap();
+        let message = |result: Result<Layout, Error>| match result.unwrap_err() {
+            Error::Invalid(message) => message,
+            other => panic!("Expected an invalid layout, got {}", other),
+        };
+
+        assert!(message(rearrange(&claims, 5, 3, 1)).starts_with("no layout exists: the claims cover 18"));
+        assert!(message(rearrange(&claims, 2, 9, 1)).starts_with("no layout exists: claim #1 is 3x3"));
+
+        // Small enough in all, but the 2x2 claim never fits beside the 3x3
+        let claims = claims_from("#1 @ 0,0: 3x3\n#2 @ 0,0: 2x2\n").unwrap();
+        assert!(message(rearrange(&claims, 4, 4, 1)).starts_with("couldn't find a layout"));
+    }
+}
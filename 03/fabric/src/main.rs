@@ -0,0 +1,85 @@
+use fabric::sheet::{Command, FabricSheet};
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage: fabric [claims]
+
+Starts with the claims given, if any, then takes commands a line at a time:
+
+  add #7 @ 1,1: 3x3   puts a claim on the sheet
+  remove #2           takes a claim off it
+  resize #2 5x5       changes a claim's size, keeping its top left corner
+  status              prints the overlapped area and the uncontested claims
+  help                prints this
+  quit                stops, as does the end of the input";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() > 1 || args.iter().any(|arg| arg.starts_with("--")) {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+
+    let mut sheet = match args.first() {
+        Some(path) => {
+            let input = aoc_common::read_input_from(Some(path));
+            let claims = aoc_common::unwrap_or_exit(overlaps::claims_from(&input));
+            aoc_common::unwrap_or_exit(FabricSheet::from_claims(claims))
+        },
+        None => FabricSheet::new(),
+    };
+
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if prompt {
+            print!("> ");
+            io::stdout().flush().unwrap();
+        }
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(error)) => {
+                eprintln!("Failed to read a command: {}", error);
+                process::exit(1);
+            },
+            None => break,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let result = match line.parse() {
+            Ok(Command::Add(claim)) => sheet.add(claim),
+            Ok(Command::Remove(claim_id)) => sheet.remove(claim_id).map(|_| ()),
+            Ok(Command::Resize(claim_id, width, height)) => sheet.resize(claim_id, width, height),
+            Ok(Command::Status) => {
+                print_status(&sheet);
+                Ok(())
+            },
+            Ok(Command::Help) => {
+                println!("{}", USAGE);
+                Ok(())
+            },
+            Ok(Command::Quit) => break,
+            Err(message) => {
+                eprintln!("{}", message);
+                continue;
+            },
+        };
+
+        if let Err(error) = result {
+            eprintln!("{}", error);
+        }
+    }
+}
+
+fn print_status(sheet: &FabricSheet) {
+    let uncontested: Vec<String> = sheet.uncontested().iter().map(|claim_id| format!("#{}", claim_id)).collect();
+    println!("claims: {}", sheet.len());
+    println!("overlapped: {}", sheet.overlapped_area());
+    println!("uncontested: {}", uncontested.join(" "));
+}
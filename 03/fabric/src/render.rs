@@ -0,0 +1,254 @@
+//! Pictures of the fabric, for seeing where claims pile up.
+
+use aoc_common::Error;
+use image::{Rgb, RgbImage};
+use overlaps::sweep;
+use overlaps::Claim;
+
+/// The most square inches a picture is drawn across or down, twice the
+/// puzzle's fabric, which keeps the counts and the picture to a few dozen
+/// megabytes.
+pub const MAX_SIDE: i64 = 2_000;
+
+const EMPTY: Rgb<u8> = Rgb([0, 0, 0]);
+const CLAIMED: Rgb<u8> = Rgb([40, 70, 160]);
+const OVERLAPPED: Rgb<u8> = Rgb([255, 220, 0]);
+const MOST_OVERLAPPED: Rgb<u8> = Rgb([255, 0, 0]);
+const OUTLINE: Rgb<u8> = Rgb([0, 255, 0]);
+
+/// The part of the fabric a picture shows, a pixel to the square inch: from
+/// the corner of the fabric to the far edges of the claims, stretched to
+/// take in any claims above or left of the corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extent {
+    pub left: i64,
+    pub top: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Extent {
+    pub fn of(claims: &[Claim]) -> Result<Extent, Error> {
+        let left = claims.iter().map(|claim| claim.x).min().unwrap_or(0).min(0);
+        let top = claims.iter().map(|claim| claim.y).min().unwrap_or(0).min(0);
+        let right = claims.iter().map(|claim| claim.right).max().unwrap_or(0).max(0);
+        let bottom = claims.iter().map(|claim| claim.bottom).max().unwrap_or(0).max(0);
+
+        let width = right as i128 - left as i128;
+        let height = bottom as i128 - top as i128;
+        if width > MAX_SIDE as i128 || height > MAX_SIDE as i128 {
+            return Err(Error::Invalid(format!("the claims cover {}x{} square inches, too much to draw", width, height)));
+        }
+        Ok(Extent { left, top, width: width as i64, height: height as i64 })
+    }
+}
+
+/// How many claims cover each square inch of `extent`, a row at a time.
+/// Keeps a single row of how many more claims cover each column than the
+/// one left of it, changing it where claims start and stop, and adds it up
+/// along each row in turn.
+pub fn density(claims: &[Claim], extent: &Extent) -> Vec<u32> {
+    let width = extent.width as usize;
+    let height = extent.height as usize;
+    let mut changes: Vec<Vec<(usize, usize, i64)>> = vec![Vec::new(); height + 1];
+    for claim in claims.iter().filter(|claim| claim.area() > 0) {
+        let left = (claim.x - extent.left) as usize;
+        let top = (claim.y - extent.top) as usize;
+        let right = (claim.right - extent.left) as usize;
+        let bottom = (claim.bottom - extent.top) as usize;
+        changes[top].push((left, right, 1));
+        changes[bottom].push((left, right, -1));
+    }
+
+    let mut columns = vec![0i64; width + 1];
+    let mut counts = Vec::with_capacity(width * height);
+    for row in changes.iter().take(height) {
+        for &(left, right, change) in row {
+            columns[left] += change;
+            columns[right] -= change;
+        }
+        let mut count = 0;
+        for column in &columns[..width] {
+            count += column;
+            counts.push(count as u32);
+        }
+    }
+    counts
+}
+
+/// The colour of a square inch `count` claims cover: black where nobody
+/// wants it, blue where one claim does, and from yellow to red as more
+/// claims pile on.
+fn colour(count: u32, most: u32) -> Rgb<u8> {
+    match count {
+        0 => EMPTY,
+        1 => CLAIMED,
+        _ if most <= 2 => OVERLAPPED,
+        _ => {
+            let t = (count - 2) as f64 / (most - 2) as f64;
+            let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+            Rgb([
+                mix(OVERLAPPED[0], MOST_OVERLAPPED[0]),
+                mix(OVERLAPPED[1], MOST_OVERLAPPED[1]),
+                mix(OVERLAPPED[2], MOST_OVERLAPPED[2]),
+            ])
+        },
+    }
+}
+
+/// Draws how many claims cover each square inch of the fabric, with the
+/// claims that overlap nothing outlined if `outline` is set.
+pub fn heatmap(claims: &[Claim], outline: bool) -> Result<RgbImage, Error> {
+    let extent = Extent::of(claims)?;
+    let counts = density(claims, &extent);
+    let most = counts.iter().cloned().max().unwrap_or(0);
+
+    let mut image = RgbImage::new(extent.width as u32, extent.height as u32);
+    for (index, count) in counts.iter().enumerate() {
+        let x = index % extent.width as usize;
+        let y = index / extent.width as usize;
+        image.put_pixel(x as u32, y as u32, colour(*count, most));
+    }
+
+    if outline {
+        for index in sweep::uncontested(claims) {
+            let claim = &claims[index];
+            if claim.area() == 0 {
+                continue;
+            }
+            let left = (claim.x - extent.left) as u32;
+            let top = (claim.y - extent.top) as u32;
+            let right = (claim.right - extent.left) as u32 - 1;
+            let bottom = (claim.bottom - extent.top) as u32 - 1;
+            for x in left..=right {
+                image.put_pixel(x, top, OUTLINE);
+                image.put_pixel(x, bottom, OUTLINE);
+            }
+            for y in top..=bottom {
+                image.put_pixel(left, y, OUTLINE);
+                image.put_pixel(right, y, OUTLINE);
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+/// An SVG the size of the heatmap with every claim outlined and labelled
+/// with its id, drawn over the heatmap at `background` if given.
+pub fn labels(claims: &[Claim], background: Option<&str>) -> Result<String, Error> {
+    let extent = Extent::of(claims)?;
+    let mut uncontested = vec![false; claims.len()];
+    for index in sweep::uncontested(claims) {
+        uncontested[index] = true;
+    }
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">\n",
+        extent.width, extent.height, extent.left, extent.top, extent.width, extent.height);
+    if let Some(background) = background {
+        svg += &format!(
+            "  <image href=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" style=\"image-rendering: pixelated\"/>\n",
+            escape(background), extent.left, extent.top, extent.width, extent.height);
+    }
+
+    for (index, claim) in claims.iter().enumerate() {
+        let stroke = if uncontested[index] { "lime" } else { "white" };
+        svg += &format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"0.25\"/>\n",
+            claim.x, claim.y, claim.width, claim.height, stroke);
+        svg += &format!(
+            "  <text x=\"{}\" y=\"{}\" font-size=\"{:.1}\" fill=\"{}\" dominant-baseline=\"hanging\">#{}</text>\n",
+            claim.x, claim.y, label_size(claim), stroke, claim.claim_id);
+    }
+
+    svg += "</svg>\n";
+    Ok(svg)
+}
+
+/// Labels fit inside their claims where they can, without getting too
+/// small to read when zoomed in.
+fn label_size(claim: &Claim) -> f64 {
+    (claim.height.min(claim.width) as f64 / 3.0).clamp(1.0, 10.0)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use overlaps::claims_from;
+
+    const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
+    #[test]
+    fn test_extent() {
+        let claims = claims_from(EXAMPLE).unwrap();
+        assert_eq!(Extent { left: 0, top: 0, width: 7, height: 7 }, Extent::of(&claims).unwrap());
+
+        let claims = claims_from("#1 @ -2,3: 4x4").unwrap();
+        assert_eq!(Extent { left: -2, top: 0, width: 4, height: 7 }, Extent::of(&claims).unwrap());
+
+        let claims = claims_from("#1 @ 0,0: 2000x1").unwrap();
+        assert!(Extent::of(&claims).is_ok());
+        let claims = claims_from("#1 @ 0,0: 2001x1").unwrap();
+        assert!(Extent::of(&claims).is_err());
+    }
+
+    #[test]
+    fn test_density() {
+        let claims = claims_from(EXAMPLE).unwrap();
+        let extent = Extent::of(&claims).unwrap();
+        let counts = density(&claims, &extent);
+
+        let expected = "\
+            0000000\
+            0001111\
+            0001111\
+            0112211\
+            0112211\
+            0111111\
+            0111111";
+        let counts: String = counts.iter().map(|count| count.to_string()).collect();
+        assert_eq!(expected, counts);
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let claims = claims_from(EXAMPLE).unwrap();
+        let image = heatmap(&claims, false).unwrap();
+        assert_eq!((7, 7), image.dimensions());
+        assert_eq!(EMPTY, *image.get_pixel(0, 0));
+        assert_eq!(CLAIMED, *image.get_pixel(1, 3));
+        assert_eq!(OVERLAPPED, *image.get_pixel(3, 3));
+        assert_eq!(CLAIMED, *image.get_pixel(6, 6));
+
+        let image = heatmap(&claims, true).unwrap();
+        assert_eq!(OUTLINE, *image.get_pixel(5, 5));
+        assert_eq!(OUTLINE, *image.get_pixel(6, 6));
+        assert_eq!(CLAIMED, *image.get_pixel(1, 3));
+    }
+
+    #[test]
+    fn test_colour() {
+        assert_eq!(OVERLAPPED, colour(2, 2));
+        assert_eq!(OVERLAPPED, colour(2, 4));
+        assert_eq!(Rgb([255, 110, 0]), colour(3, 4));
+        assert_eq!(MOST_OVERLAPPED, colour(4, 4));
+    }
+
+    #[test]
+    fn test_labels() {
+        let claims = claims_from(EXAMPLE).unwrap();
+        let svg = labels(&claims, Some("fabric.png")).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"7\" height=\"7\" viewBox=\"0 0 7 7\">\n"));
+        assert!(svg.contains("<image href=\"fabric.png\""));
+        assert!(svg.contains(">#1</text>"));
+        assert!(svg.contains("<rect x=\"5\" y=\"5\" width=\"2\" height=\"2\" fill=\"none\" stroke=\"lime\""));
+        assert!(svg.contains("<rect x=\"1\" y=\"3\" width=\"4\" height=\"4\" fill=\"none\" stroke=\"white\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
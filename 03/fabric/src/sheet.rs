@@ -0,0 +1,344 @@
+//! A sheet of fabric whose claims change one at a time, keeping track of
+//! the overlaps as they go rather than working them all out again.
+
+use aoc_common::Error;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use overlaps::sweep;
+use overlaps::Claim;
+
+/// Claims on a sheet of fabric, by id, with which of them overlap and by
+/// how much in all.
+#[derive(Debug, Default)]
+pub struct FabricSheet {
+    claims: BTreeMap<i32, Claim>,
+    overlaps: BTreeMap<i32, BTreeSet<i32>>,
+    overlapped_area: i128,
+}
+
+impl FabricSheet {
+    pub fn new() -> FabricSheet {
+        FabricSheet::default()
+    }
+
+    /// A sheet holding `claims`, with their overlaps found in one sweep.
+    pub fn from_claims(claims: Vec<Claim>) -> Result<FabricSheet, Error> {
+        let mut sheet = FabricSheet::new();
+        for (first, second) in sweep::overlapping_pairs(&claims) {
+            let (first, second) = (claims[first].claim_id, claims[second].claim_id);
+            sheet.overlaps.entry(first).or_default().insert(second);
+            sheet.overlaps.entry(second).or_default().insert(first);
+        }
+        sheet.overlapped_area = sweep::overlapped_area(&claims).ok_or_else(too_big)?;
+
+        for claim in claims {
+            let claim_id = claim.claim_id;
+            if sheet.claims.insert(claim_id, claim).is_some() {
+                return Err(duplicate(claim_id));
+            }
+            sheet.overlaps.entry(claim_id).or_default();
+        }
+        Ok(sheet)
+    }
+
+    pub fn get(&self, claim_id: i32) -> Option<&Claim> {
+        self.claims.get(&claim_id)
+    }
+
+    /// The claims on the sheet, by id.
+    pub fn claims(&self) -> impl Iterator<Item = &Claim> {
+        self.claims.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.claims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.claims.is_empty()
+    }
+
+    /// The area of fabric within two or more claims.
+    pub fn overlapped_area(&self) -> i128 {
+        self.overlapped_area
+    }
+
+    /// The ids of the claims that overlap no other, in order.
+    pub fn uncontested(&self) -> Vec<i32> {
+        self.overlaps.iter()
+            .filter(|(_, others)| others.is_empty())
+            .map(|(claim_id, _)| *claim_id)
+            .collect()
+    }
+
+    /// The ids of the claims overlapping the claim with `claim_id`, in
+    /// order.
+    pub fn overlaps(&self, claim_id: i32) -> Option<&BTreeSet<i32>> {
+        self.overlaps.get(&claim_id)
+    }
+
+    /// Puts a claim on the sheet, or leaves the sheet as it was if the
+    /// claim is already there or the overlapped area would get too big.
+    /// Finding the claims it overlaps means checking every claim, then
+    /// sweeping just those.
+    pub fn add(&mut self, claim: Claim) -> Result<(), Error> {
+        if self.claims.contains_key(&claim.claim_id) {
+            return Err(duplicate(claim.claim_id));
+        }
+
+        let others: BTreeSet<i32> = self.claims.values()
+            .filter(|other| claim.intersection(other).is_some())
+            .map(|other| other.claim_id)
+            .collect();
+        let grown = self.covered_once_within(&claim, &others)?;
+        self.overlapped_area = self.overlapped_area.checked_add(grown).ok_or_else(too_big)?;
+
+        for other in &others {
+            self.overlaps.get_mut(other).unwrap().insert(claim.claim_id);
+        }
+        self.overlaps.insert(claim.claim_id, others);
+        self.claims.insert(claim.claim_id, claim);
+        Ok(())
+    }
+
+    /// Takes the claim with `claim_id` off the sheet.
+    pub fn remove(&mut self, claim_id: i32) -> Result<Claim, Error> {
+        let claim = self.claims.get(&claim_id).ok_or_else(|| missing(claim_id))?;
+        let shrunk = self.covered_once_within(claim, &self.overlaps[&claim_id])?;
+        self.overlapped_area = self.overlapped_area.checked_sub(shrunk).ok_or_else(too_big)?;
+
+        let claim = self.claims.remove(&claim_id).unwrap();
+        let others = self.overlaps.remove(&claim_id).unwrap();
+
+        for other in &others {
+            self.overlaps.get_mut(other).unwrap().remove(&claim_id);
+        }
+        Ok(claim)
+    }
+
+    /// Changes the size of the claim with `claim_id`, keeping its top left
+    /// corner where it is. If the resized claim doesn't fit, the claim stays
+    /// the size it was.
+    pub fn resize(&mut self, claim_id: i32, width: i64, height: i64) -> Result<(), Error> {
+        let claim = self.claims.get(&claim_id).ok_or_else(|| missing(claim_id))?;
+        let resized = Claim::new(claim_id, claim.x, claim.y, width, height)
+            .ok_or_else(|| Error::Invalid(format!("claim #{} can't be {}x{}, its far edges would be out of range", claim_id, width, height)))?;
+
+        let claim = self.remove(claim_id)?;
+        match self.add(resized) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                // The claim fitted before, so it fits back in again
+                self.add(claim).unwrap();
+                Err(error)
+            },
+        }
+    }
+
+    /// How much of `claim` exactly one of `others` covers, which is how much
+    /// the overlapped area grows by with the claim added, or shrinks by with
+    /// it taken away.
+    fn covered_once_within(&self, claim: &Claim, others: &BTreeSet<i32>) -> Result<i128, Error> {
+        let shared: Vec<Claim> = others.iter()
+            .map(|other| claim.intersection(&self.claims[other]).unwrap())
+            .collect();
+        let covered = sweep::covered_area(&shared).ok_or_else(too_big)?;
+        Ok(covered - sweep::overlapped_area(&shared).ok_or_else(too_big)?)
+    }
+}
+
+fn duplicate(claim_id: i32) -> Error {
+    Error::Invalid(format!("there's already a claim #{}", claim_id))
+}
+
+fn missing(claim_id: i32) -> Error {
+    Error::Invalid(format!("there's no claim #{}", claim_id))
+}
+
+fn too_big() -> Error {
+    Error::Invalid(String::from("the overlapped area would be too big to work out"))
+}
+
+/// Something to do to a sheet, like `add #7 @ 1,1: 3x3`, `remove #2`,
+/// `resize #2 5x5` or `status`.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Add(Claim),
+    Remove(i32),
+    Resize(i32, i64, i64),
+    Status,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Command, String> {
+        let string = string.trim();
+        let (name, rest) = match string.find(' ') {
+            Some(space) => (&string[..space], string[space..].trim()),
+            None => (string, ""),
+        };
+
+        let command = match name {
+            "add" => Command::Add(rest.parse().map_err(|_| format!("Expected a claim like '#1 @ 1,3: 4x4', got '{}'", rest))?),
+            "remove" => Command::Remove(claim_id(rest)?),
+            "resize" => {
+                let mut parts = rest.split_whitespace();
+                let claim_id = claim_id(parts.next().unwrap_or(""))?;
+                let size = parts.next().unwrap_or("");
+                let (width, height) = parse_size(size).ok_or_else(|| format!("Expected a size like 4x4, got '{}'", size))?;
+                if let Some(extra) = parts.next() {
+                    return Err(format!("Unexpected '{}' after the size", extra));
+                }
+                Command::Resize(claim_id, width, height)
+            },
+            "status" => Command::Status,
+            "help" => Command::Help,
+            "quit" => Command::Quit,
+            _ => return Err(format!("Unknown command '{}'", name)),
+        };
+
+        if !rest.is_empty() && (command == Command::Status || command == Command::Help || command == Command::Quit) {
+            return Err(format!("Unexpected '{}' after {}", rest, name));
+        }
+        Ok(command)
+    }
+}
+
+fn claim_id(text: &str) -> Result<i32, String> {
+    let id = text.strip_prefix('#').unwrap_or(text);
+    id.parse().map_err(|_| format!("Expected a claim id like #2, got '{}'", text))
+}
+
+fn parse_size(text: &str) -> Option<(i64, i64)> {
+    let cross = text.find('x')?;
+    Some((text[..cross].parse().ok()?, text[(cross + 1)..].parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use overlaps::claims_from;
+
+    fn claim(text: &str) -> Claim {
+        text.parse().unwrap()
+    }
+
+    /// Checks the sheet against working everything out from scratch.
+    fn check(sheet: &FabricSheet) {
+        let claims: Vec<Claim> = sheet.claims().cloned().collect();
+        assert_eq!(sweep::overlapped_area(&claims), Some(sheet.overlapped_area()));
+
+        let uncontested: Vec<i32> = sweep::uncontested(&claims).into_iter().map(|index| claims[index].claim_id).collect();
+        assert_eq!(uncontested, sheet.uncontested());
+    }
+
+    #[test]
+    fn test_add() {
+        let mut sheet = FabricSheet::new();
+        sheet.add(claim("#1 @ 1,3: 4x4")).unwrap();
+        sheet.add(claim("#2 @ 3,1: 4x4")).unwrap();
+        sheet.add(claim("#3 @ 5,5: 2x2")).unwrap();
+        assert_eq!(4, sheet.overlapped_area());
+        assert_eq!(vec![3], sheet.uncontested());
+
+        // Covers the overlap again, and a square of claim 1 only
+        sheet.add(claim("#4 @ 2,3: 3x2")).unwrap();
+        check(&sheet);
+        assert_eq!(6, sheet.overlapped_area());
+        assert_eq!(Some(&[1, 2].iter().cloned().collect()), sheet.overlaps(4));
+
+        assert!(sheet.add(claim("#4 @ 0,0: 1x1")).is_err());
+    }
+
+    #[test]
+    fn test_remove() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 2,3: 3x2\n").unwrap();
+        let mut sheet = FabricSheet::from_claims(claims).unwrap();
+        check(&sheet);
+
+        assert_eq!(claim("#2 @ 3,1: 4x4"), sheet.remove(2).unwrap());
+        check(&sheet);
+        assert_eq!(6, sheet.overlapped_area());
+
+        sheet.remove(4).unwrap();
+        check(&sheet);
+        assert_eq!(0, sheet.overlapped_area());
+        assert_eq!(vec![1, 3], sheet.uncontested());
+
+        assert!(sheet.remove(4).is_err());
+    }
+
+    #[test]
+    fn test_resize() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+        let mut sheet = FabricSheet::from_claims(claims).unwrap();
+
+        sheet.resize(3, 1, 1).unwrap();
+        check(&sheet);
+        sheet.resize(2, 2, 2).unwrap();
+        check(&sheet);
+        assert_eq!(0, sheet.overlapped_area());
+        assert_eq!(vec![1, 2, 3], sheet.uncontested());
+
+        assert!(sheet.resize(7, 2, 2).is_err());
+        assert!(sheet.resize(2, i64::MAX, 2).is_err());
+        assert_eq!(Some(&claim("#2 @ 3,1: 2x2")), sheet.get(2));
+    }
+
+    /// Claims out to `i64::MAX` from the middle, the first four covering
+    /// two quarters of the fabric twice over.
+    fn huge_claims() -> Vec<Claim> {
+        let max = i64::MAX;
+        claims_from(&format!("\
+#1 @ 0,0: {m}x{m}
+#2 @ 0,0: {m}x{m}
+#3 @ -{m},0: {m}x{m}
+#4 @ -{m},0: {m}x{m}
+#5 @ -{m},-{m}: 1x1
+#6 @ -{m},-{m}: {m}x{m}
+#7 @ -{m},-{m}: {m}x{m}
+", m = max)).unwrap()
+    }
+
+    #[test]
+    fn test_overlapped_area_too_big() {
+        let claims = huge_claims();
+        let mut sheet = FabricSheet::from_claims(claims[..6].to_vec()).unwrap();
+        let area = sheet.overlapped_area();
+
+        // A third quarter covered twice is more than fits in an i128
+        assert!(sheet.add(claims[6].clone()).is_err());
+        assert!(sheet.resize(5, i64::MAX, i64::MAX).is_err());
+        assert_eq!(area, sheet.overlapped_area());
+        assert_eq!(Some(&claims[4]), sheet.get(5));
+        assert_eq!(None, sheet.get(7));
+        check(&sheet);
+
+        assert!(FabricSheet::from_claims(claims).is_err());
+    }
+
+    #[test]
+    fn test_from_claims_rejects_duplicates() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#1 @ 3,1: 4x4\n").unwrap();
+        assert!(FabricSheet::from_claims(claims).is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Ok(Command::Add(claim("#7 @ 1,1: 3x3"))), "add #7 @ 1,1: 3x3".parse());
+        assert_eq!(Ok(Command::Remove(2)), "remove #2".parse());
+        assert_eq!(Ok(Command::Resize(2, 5, 6)), " resize #2 5x6 ".parse());
+        assert_eq!(Ok(Command::Status), "status".parse());
+        assert_eq!(Ok(Command::Quit), "quit".parse());
+
+        assert!("add #7".parse::<Command>().is_err());
+        assert!("remove two".parse::<Command>().is_err());
+        assert!("resize #2".parse::<Command>().is_err());
+        assert!("resize #2 5x6 7".parse::<Command>().is_err());
+        assert!("status now".parse::<Command>().is_err());
+        assert!("move #2".parse::<Command>().is_err());
+    }
+}
@@ -0,0 +1,154 @@
+extern crate aoc_common;
+extern crate overlaps;
+
+use aoc_common::{Answer, Error, Solution};
+use overlaps::{sweep, Claim};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process_lines(input.to_string())?.into())
+    }
+}
+
+pub fn process_lines(input: String) -> Result<i32, Error> {
+    let claims = claims_from(input)?;
+
+    match sweep::uncontested(&claims).first() {
+        Some(index) => Ok(claims[*index].claim_id),
+        None => Ok(-1),
+    }
+}
+
+fn claims_from(lines: String) -> Result<Vec<Claim>, Error> {
+    overlaps::claims_from(&lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseError;
+
+    #[test]
+    fn test_parsing_claim_from_string() {
+        let input = String::from("#123 @ 4,56: 789x101112");
+        let result = input.parse::<Claim>().unwrap();
+
+        assert_eq!(123, result.claim_id);
+        assert_eq!(4, result.x);
+        assert_eq!(56, result.y);
+        assert_eq!(789, result.width);
+        assert_eq!(101_112, result.height);
+        assert_eq!(793, result.right);
+        assert_eq!(101_168, result.bottom);
+    }
+
+    #[test]
+    fn test_parsing_claim_from_string2() {
+        let input = String::from("#1 @ 1,3: 4x4");
+        let result = input.parse::<Claim>().unwrap();
+
+        assert_eq!(1, result.claim_id);
+        assert_eq!(1, result.x);
+        assert_eq!(3, result.y);
+        assert_eq!(4, result.width);
+        assert_eq!(4, result.height);
+        assert_eq!(5, result.right);
+        assert_eq!(7, result.bottom);
+    }
+
+    #[test]
+    fn test_parsing() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n");
+        let result = claims_from(input).unwrap();
+
+        assert_eq!(3, result.len());
+
+        assert_eq!(1, result[0].claim_id);
+        assert_eq!(1, result[0].x);
+        assert_eq!(3, result[0].y);
+        assert_eq!(4, result[0].width);
+        assert_eq!(4, result[0].height);
+        assert_eq!(5, result[0].right);
+        assert_eq!(7, result[0].bottom);
+
+        assert_eq!(2, result[1].claim_id);
+        assert_eq!(3, result[1].x);
+        assert_eq!(1, result[1].y);
+        assert_eq!(4, result[1].width);
+        assert_eq!(4, result[1].height);
+        assert_eq!(7, result[1].right);
+        assert_eq!(5, result[1].bottom);
+
+        assert_eq!(3, result[2].claim_id);
+        assert_eq!(5, result[2].x);
+        assert_eq!(5, result[2].y);
+        assert_eq!(2, result[2].width);
+        assert_eq!(2, result[2].height);
+        assert_eq!(7, result[2].right);
+        assert_eq!(7, result[2].bottom);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let input = String::from("#1 @ 1,1: 2x2");
+        let claim = input.parse::<Claim>().unwrap();
+
+        assert!(!claim.contains_point(0, 0));
+        assert!(!claim.contains_point(1, 0));
+        assert!(!claim.contains_point(2, 0));
+        assert!(!claim.contains_point(3, 0));
+
+        assert!(!claim.contains_point(0, 1));
+        assert!(claim.contains_point(1, 1));
+        assert!(claim.contains_point(2, 1));
+        assert!(!claim.contains_point(3, 1));
+
+        assert!(!claim.contains_point(0, 2));
+        assert!(claim.contains_point(1, 2));
+        assert!(claim.contains_point(2, 2));
+        assert!(!claim.contains_point(3, 2));
+
+        assert!(!claim.contains_point(0, 3));
+        assert!(!claim.contains_point(1, 3));
+        assert!(!claim.contains_point(2, 3));
+        assert!(!claim.contains_point(3, 3));
+    }
+
+    #[test]
+    fn test_example() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n");
+        let result = process_lines(input).unwrap();
+
+        assert_eq!(3, result);
+    }
+
+    #[test]
+    fn test_parsing_reports_bad_claims() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3;1: 4x4\n#3 @ 5,5: 2xtwo\n");
+        let result = claims_from(input);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(1, "#2 @ 3;1: 4x4", "expected a claim like '#1 @ 1,3: 4x4'").at_line(2),
+            ParseError::new(13, "two", "expected a height").at_line(3),
+        ]);
+        assert_eq!(Err(expected), result.map(|claims| claims.len()));
+    }
+}
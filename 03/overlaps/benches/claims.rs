@@ -0,0 +1,63 @@
+#[macro_use]
+extern crate criterion;
+extern crate overlaps;
+extern crate regex;
+
+use criterion::{Criterion, Throughput};
+use overlaps::Claim;
+use regex::Regex;
+use std::fmt::Write;
+
+/// Builds `count` claims spread over the fabric, one per line.
+fn claims_text(count: usize) -> String {
+    let mut text = String::new();
+    for index in 0..count {
+        let x = index * 7 % 990;
+        let y = index * 13 % 990;
+        let width = 1 + index % 29;
+        let height = 1 + index % 23;
+        writeln!(text, "#{} @ {},{}: {}x{}", index + 1, x, y, width, height).unwrap();
+    }
+    text
+}
+
+/// Parses a claim the way it used to be, compiling the regex afresh each
+/// time, to measure what that costs.
+fn claim_from_line(line: &str) -> Claim {
+    let re = Regex::new(r"#(?P<id>.\d*) @ (?P<x>\d*),(?P<y>\d*): (?P<width>\d*)x(?P<height>\d*)").unwrap();
+    let captures = re.captures(line).unwrap();
+
+    let claim_id = captures["id"].parse().unwrap();
+    let x = captures["x"].parse().unwrap();
+    let y = captures["y"].parse().unwrap();
+    let width = captures["width"].parse().unwrap();
+    let height = captures["height"].parse().unwrap();
+    Claim::new(claim_id, x, y, width, height).unwrap()
+}
+
+fn bench_claim_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("claim parsing");
+    group.sample_size(10);
+
+    // The regex is compiled once and shared by every line
+    let lines = 1_000_000;
+    let text = claims_text(lines);
+    group.throughput(Throughput::Elements(lines as u64));
+    group.bench_function("cached regex, 1M lines", |b| {
+        b.iter(|| overlaps::claims_from(&text).unwrap())
+    });
+
+    // What parsing used to cost, compiling the regex again for every line.
+    // Far fewer lines, as a million would take minutes per sample.
+    let lines = 10_000;
+    let text = claims_text(lines);
+    group.throughput(Throughput::Elements(lines as u64));
+    group.bench_function("regex per line, 10k lines", |b| {
+        b.iter(|| text.lines().map(claim_from_line).collect::<Vec<Claim>>())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_claim_parsing);
+criterion_main!(benches);
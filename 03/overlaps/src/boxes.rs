@@ -0,0 +1,373 @@
+//! Claims with any number of dimensions, for fabric that isn't flat. A
+//! claim on an ordinary sheet is a box with two.
+
+use aoc_common::{Error, ParseError};
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use sweep;
+use Claim;
+
+/// A box some elf would like to use, from its near corner along each axis
+/// by its size along that axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxClaim {
+    pub claim_id: i32,
+    pub corner: Vec<i64>,
+    pub size: Vec<i64>,
+    /// The corner opposite `corner`, where the box stops.
+    pub far: Vec<i64>,
+}
+
+/// A box, like `#1 @ 1,3,5: 4x4x2`.
+static BOX_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#(?P<id>[^ ]*) @ (?P<corner>[^:]*): (?P<size>.*)$").unwrap());
+
+impl FromStr for BoxClaim {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<BoxClaim, ParseError> {
+        let captures = match BOX_RE.captures(string) {
+            Some(captures) => captures,
+            None => return Err(ParseError::new(1, string, "expected a box like '#1 @ 1,3,5: 4x4x2'")),
+        };
+
+        let claim_id = aoc_common::parse_field(string, captures.name("id").unwrap().as_str(), "a claim id")?;
+        let corner = captures.name("corner").unwrap().as_str().split(',')
+            .map(|field| aoc_common::parse_field(string, field, "a coordinate"))
+            .collect::<Result<Vec<i64>, ParseError>>()?;
+        let sizes = captures.name("size").unwrap().as_str();
+        let size = sizes.split('x')
+            .map(|field| aoc_common::parse_field(string, field, "a size"))
+            .collect::<Result<Vec<i64>, ParseError>>()?;
+
+        if size.len() != corner.len() {
+            let reason = format!("expected a size for each of the {} coordinates", corner.len());
+            return Err(ParseError::within(string, sizes, &reason));
+        }
+        BoxClaim::new(claim_id, corner, size)
+            .ok_or_else(|| ParseError::new(1, string, "expected a box with its far edges in range"))
+    }
+}
+
+impl fmt::Display for BoxClaim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let corner: Vec<String> = self.corner.iter().map(|coordinate| coordinate.to_string()).collect();
+        let size: Vec<String> = self.size.iter().map(|size| size.to_string()).collect();
+        write!(f, "#{} @ {}: {}", self.claim_id, corner.join(","), size.join("x"))
+    }
+}
+
+impl BoxClaim {
+    /// A box, unless it has no dimensions, its corner and size have
+    /// different numbers of them, or its far corner is too far out to work
+    /// with.
+    pub fn new(claim_id: i32, corner: Vec<i64>, size: Vec<i64>) -> Option<BoxClaim> {
+        if corner.is_empty() || corner.len() != size.len() {
+            return None;
+        }
+        let far = corner.iter().zip(size.iter())
+            .map(|(coordinate, size)| coordinate.checked_add(*size))
+            .collect::<Option<Vec<i64>>>()?;
+        Some(BoxClaim { claim_id, corner, size, far })
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.corner.len()
+    }
+
+    /// Boxes with no size along any axis cover nothing.
+    pub fn is_empty(&self) -> bool {
+        self.size.iter().any(|size| *size <= 0)
+    }
+
+    /// Whether the boxes share any space, rather than just touching.
+    pub fn overlaps(&self, other: &BoxClaim) -> bool {
+        !self.is_empty() && !other.is_empty() && self.overlaps_along(other, 0)
+    }
+
+    /// Whether the boxes meet along every axis from `from` on.
+    fn overlaps_along(&self, other: &BoxClaim, from: usize) -> bool {
+        (from..self.dimensions()).all(|axis| self.corner[axis] < other.far[axis] && other.corner[axis] < self.far[axis])
+    }
+
+    /// The box with its first axis left out.
+    fn cross_section(&self) -> BoxClaim {
+        BoxClaim {
+            claim_id: self.claim_id,
+            corner: self.corner[1..].to_vec(),
+            size: self.size[1..].to_vec(),
+            far: self.far[1..].to_vec(),
+        }
+    }
+
+    fn to_claim(&self) -> Claim {
+        Claim {
+            claim_id: self.claim_id,
+            x: self.corner[0],
+            y: self.corner[1],
+            width: self.size[0],
+            height: self.size[1],
+            right: self.far[0],
+            bottom: self.far[1],
+        }
+    }
+}
+
+impl From<Claim> for BoxClaim {
+    fn from(claim: Claim) -> BoxClaim {
+        BoxClaim {
+            claim_id: claim.claim_id,
+            corner: vec![claim.x, claim.y],
+            size: vec![claim.width, claim.height],
+            far: vec![claim.right, claim.bottom],
+        }
+    }
+}
+
+/// Parses one box per line of `lines`.
+pub fn boxes_from(lines: &str) -> Result<Vec<BoxClaim>, Error> {
+    aoc_common::parse_lines(lines)
+}
+
+/// How many dimensions the boxes have, checking they all have the same.
+pub fn dimensions(boxes: &[BoxClaim]) -> Result<usize, Error> {
+    let first = match boxes.first() {
+        Some(first) => first,
+        None => return Ok(0),
+    };
+    match boxes.iter().find(|other| other.dimensions() != first.dimensions()) {
+        Some(other) => Err(Error::Invalid(format!(
+            "box #{} has {} dimensions, but box #{} has {}",
+            other.claim_id, other.dimensions(), first.claim_id, first.dimensions()))),
+        None => Ok(first.dimensions()),
+    }
+}
+
+fn too_big() -> Error {
+    Error::Invalid(String::from("the overlapped volume is too big to work out in 128 bits"))
+}
+
+/// The volume within two or more boxes. Flat boxes are swept as claims are;
+/// boxes of more dimensions are swept along their first axis, working out
+/// the overlap of the cross sections between each place the sweep stops,
+/// which takes O(n^(d - 1) log n) for n boxes of d dimensions.
+pub fn overlapped_volume(boxes: &[BoxClaim]) -> Result<i128, Error> {
+    dimensions(boxes)?;
+    let boxes: Vec<BoxClaim> = boxes.iter().filter(|claim| !claim.is_empty()).cloned().collect();
+    volume_within_two(&boxes).ok_or_else(too_big)
+}
+
+fn volume_within_two(boxes: &[BoxClaim]) -> Option<i128> {
+    let dimensions = match boxes.first() {
+        Some(first) => first.dimensions(),
+        None => return Some(0),
+    };
+    if dimensions == 2 {
+        let claims: Vec<Claim> = boxes.iter().map(|claim| claim.to_claim()).collect();
+        return sweep::overlapped_area(&claims);
+    }
+
+    // Boxes stopping somewhere stop before any start there
+    let mut events: Vec<(i64, bool, usize)> = Vec::new();
+    for (index, claim) in boxes.iter().enumerate() {
+        events.push((claim.corner[0], true, index));
+        events.push((claim.far[0], false, index));
+    }
+    events.sort_unstable();
+
+    let mut volume: i128 = 0;
+    let mut active: Vec<usize> = Vec::new();
+    let mut last = None;
+    for (position, starts, index) in events {
+        if let Some(last) = last {
+            let length = position as i128 - last as i128;
+            if length > 0 && active.len() > 1 {
+                let within_two = if dimensions == 1 {
+                    1
+                } else {
+                    let sections: Vec<BoxClaim> = active.iter().map(|index| boxes[*index].cross_section()).collect();
+                    volume_within_two(&sections)?
+                };
+                volume = volume.checked_add(within_two.checked_mul(length)?)?;
+            }
+        }
+        last = Some(position);
+
+        if starts {
+            active.push(index);
+        } else {
+            active.retain(|other| *other != index);
+        }
+    }
+    Some(volume)
+}
+
+/// Every pair of boxes that overlap, as indexes into `boxes` with the
+/// earlier first, in order. Flat boxes are swept as claims are; others are
+/// swept along their first axis, checking each box against those the sweep
+/// is passing through, which is slower where many boxes line up.
+pub fn overlapping_pairs(boxes: &[BoxClaim]) -> Result<Vec<(usize, usize)>, Error> {
+    if dimensions(boxes)? == 2 {
+        let claims: Vec<Claim> = boxes.iter().map(|claim| claim.to_claim()).collect();
+        return Ok(sweep::overlapping_pairs(&claims));
+    }
+
+    let mut order: Vec<usize> = (0..boxes.len()).filter(|index| !boxes[*index].is_empty()).collect();
+    order.sort_by_key(|index| boxes[*index].corner[0]);
+
+    let mut pairs = Vec::new();
+    let mut active: Vec<usize> = Vec::new();
+    for index in order {
+        let claim = &boxes[index];
+        active.retain(|other| boxes[*other].far[0] > claim.corner[0]);
+        for other in &active {
+            if claim.overlaps_along(&boxes[*other], 1) {
+                pairs.push((index.min(*other), index.max(*other)));
+            }
+        }
+        active.push(index);
+    }
+
+    pairs.sort_unstable();
+    Ok(pairs)
+}
+
+/// The boxes that overlap no other box, as indexes into `boxes`, in order.
+pub fn uncontested(boxes: &[BoxClaim]) -> Result<Vec<usize>, Error> {
+    let mut contested = vec![false; boxes.len()];
+    for (first, second) in overlapping_pairs(boxes)? {
+        contested[first] = true;
+        contested[second] = true;
+    }
+
+    Ok((0..boxes.len()).filter(|index| !contested[*index]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims_from;
+
+    /// The claims the 2D sweep is tested with, and the overlapped area and
+    /// uncontested claims it finds for them.
+    const FLAT_CASES: [(&str, i128, &[usize]); 4] = [
+        ("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n", 4, &[2]),
+        ("#1 @ 0,0: 2x2\n#2 @ 2,0: 2x2\n#3 @ 0,2: 2x2\n#4 @ 0,0: 0x5\n", 0, &[0, 1, 2, 3]),
+        ("#1 @ 0,0: 4x4\n#2 @ 1,1: 2x2\n#3 @ 1,1: 2x2\n#4 @ 3,3: 5x5\n", 5, &[]),
+        ("#1 @ 0,0: 3000000000x3000000000\n#2 @ 2000000000,-1000000000: 2000000000x2000000000\n", 1_000_000_000_000_000_000, &[]),
+    ];
+
+    /// Stretches every box into more dimensions, a unit deep along each.
+    fn extrude(boxes: &[BoxClaim], extra: usize) -> Vec<BoxClaim> {
+        boxes.iter()
+            .map(|claim| {
+                let mut corner = claim.corner.clone();
+                let mut size = claim.size.clone();
+                corner.extend(vec![7; extra]);
+                size.extend(vec![1; extra]);
+                BoxClaim::new(claim.claim_id, corner, size).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parsing() {
+        let claim: BoxClaim = "#1 @ 1,3,5: 4x4x2".parse().unwrap();
+        assert_eq!(BoxClaim { claim_id: 1, corner: vec![1, 3, 5], size: vec![4, 4, 2], far: vec![5, 7, 7] }, claim);
+        assert_eq!("#1 @ 1,3,5: 4x4x2", claim.to_string());
+
+        let flat: BoxClaim = "#123 @ 4,56: 789x101112".parse().unwrap();
+        assert_eq!(BoxClaim::from("#123 @ 4,56: 789x101112".parse::<Claim>().unwrap()), flat);
+    }
+
+    #[test]
+    fn test_parsing_reports_bad_boxes() {
+        let input = "#1 @ 1,3,5: 4x4x2\n#2 @ 1,3,5: 4x4\n#3 @ 1,three: 4x4\n#4 @ 1;3: 4x4\n#5 1,3: 4x4\n";
+        let expected = Error::Parse(vec![
+            ParseError::new(13, "4x4", "expected a size for each of the 3 coordinates").at_line(2),
+            ParseError::new(8, "three", "expected a coordinate").at_line(3),
+            ParseError::new(6, "1;3", "expected a coordinate").at_line(4),
+            ParseError::new(1, "#5 1,3: 4x4", "expected a box like '#1 @ 1,3,5: 4x4x2'").at_line(5),
+        ]);
+        assert_eq!(Err(expected), boxes_from(input).map(|boxes| boxes.len()));
+    }
+
+    #[test]
+    fn test_flat_boxes_agree_with_claims() {
+        for (input, area, alone) in FLAT_CASES.iter() {
+            let claims = claims_from(input).unwrap();
+            let boxes = boxes_from(input).unwrap();
+            assert_eq!(Some(*area), sweep::overlapped_area(&claims));
+            assert_eq!(*area, overlapped_volume(&boxes).unwrap());
+            assert_eq!(alone.to_vec(), uncontested(&boxes).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_extruded_boxes_keep_their_overlaps() {
+        for (input, area, alone) in FLAT_CASES.iter() {
+            for extra in 1..=2 {
+                let boxes = extrude(&boxes_from(input).unwrap(), extra);
+                assert_eq!(*area, overlapped_volume(&boxes).unwrap(), "{} extra dimensions of\n{}", extra, input);
+                assert_eq!(alone.to_vec(), uncontested(&boxes).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_three_dimensions() {
+        // The example, but with the first two claims only meeting for part
+        // of their depth
+        let boxes = boxes_from("#1 @ 1,3,0: 4x4x2\n#2 @ 3,1,1: 4x4x3\n#3 @ 5,5,0: 2x2x2\n").unwrap();
+        assert_eq!(4, overlapped_volume(&boxes).unwrap());
+        assert_eq!(vec![(0, 1)], overlapping_pairs(&boxes).unwrap());
+        assert_eq!(vec![2], uncontested(&boxes).unwrap());
+
+        // Stacked boxes only touching don't overlap
+        let boxes = boxes_from("#1 @ 0,0,0: 2x2x2\n#2 @ 0,0,2: 2x2x2\n#3 @ 1,1,1: 2x2x2\n").unwrap();
+        assert_eq!(2, overlapped_volume(&boxes).unwrap());
+        assert_eq!(vec![(0, 2), (1, 2)], overlapping_pairs(&boxes).unwrap());
+    }
+
+    #[test]
+    fn test_one_dimension() {
+        let boxes = boxes_from("#1 @ 0: 5\n#2 @ 3: 4\n#3 @ 4: 1\n#4 @ 7: 2\n").unwrap();
+        assert_eq!(2, overlapped_volume(&boxes).unwrap());
+        assert_eq!(vec![(0, 1), (0, 2), (1, 2)], overlapping_pairs(&boxes).unwrap());
+        assert_eq!(vec![3], uncontested(&boxes).unwrap());
+    }
+
+    #[test]
+    fn test_mixed_dimensions() {
+        let boxes = boxes_from("#1 @ 1,3: 4x4\n#2 @ 1,3,5: 4x4x2\n").unwrap();
+        let expected = Err(Error::Invalid(String::from("box #2 has 3 dimensions, but box #1 has 2")));
+        assert_eq!(expected, overlapped_volume(&boxes));
+        assert!(uncontested(&boxes).is_err());
+    }
+
+    #[test]
+    fn test_volume_too_big() {
+        let huge = "9000000000000000000";
+        let input = format!("#1 @ 0,0,0: {0}x{0}x{0}\n#2 @ 0,0,0: {0}x{0}x{0}\n", huge);
+        assert!(overlapped_volume(&boxes_from(&input).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_area_too_big() {
+        // Every quadrant of the fabric boxed twice, flat and as a slab one
+        // deep, whose cross-sections are too big themselves
+        let side = i64::MAX;
+        let corners = [(0, 0), (-side, 0), (-side, -side), (0, -side)];
+        let (mut flat, mut slab) = (String::new(), String::new());
+        for (index, (x, y)) in corners.iter().enumerate() {
+            for copy in 0..2 {
+                let claim_id = 2 * index + copy + 1;
+                flat += &format!("#{} @ {},{}: {}x{}\n", claim_id, x, y, side, side);
+                slab += &format!("#{} @ 0,{},{}: 1x{}x{}\n", claim_id, x, y, side, side);
+            }
+        }
+        assert_eq!(Err(too_big()), overlapped_volume(&boxes_from(&flat).unwrap()));
+        assert_eq!(Err(too_big()), overlapped_volume(&boxes_from(&slab).unwrap()));
+    }
+}
@@ -0,0 +1,180 @@
+//! Which claims overlap which, and by how much.
+
+use aoc_common::Error;
+use sweep;
+use Claim;
+
+/// Another claim a claim overlaps, as where it is in the claims, and how
+/// much fabric they share.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overlap {
+    pub claim: usize,
+    pub area: i128,
+}
+
+/// Every claim joined to the claims it overlaps.
+pub struct OverlapGraph<'a> {
+    claims: &'a [Claim],
+    overlaps: Vec<Vec<Overlap>>,
+}
+
+impl<'a> OverlapGraph<'a> {
+    /// Finds every overlapping pair of claims with a sweep, taking
+    /// O((n + k) log n) for n claims and k overlapping pairs.
+    pub fn new(claims: &'a [Claim]) -> OverlapGraph<'a> {
+        let mut overlaps = vec![Vec::new(); claims.len()];
+        for (first, second) in sweep::overlapping_pairs(claims) {
+            let area = claims[first].intersection(&claims[second]).unwrap().area();
+            overlaps[first].push(Overlap { claim: second, area });
+            overlaps[second].push(Overlap { claim: first, area });
+        }
+        OverlapGraph { claims, overlaps }
+    }
+
+    pub fn claims(&self) -> &'a [Claim] {
+        self.claims
+    }
+
+    /// Where the claim with `claim_id` is in the claims, if it's there.
+    pub fn find(&self, claim_id: i32) -> Option<usize> {
+        self.claims.iter().position(|claim| claim.claim_id == claim_id)
+    }
+
+    /// The claims overlapping the claim at `index`, in order.
+    pub fn overlaps(&self, index: usize) -> &[Overlap] {
+        &self.overlaps[index]
+    }
+
+    /// Groups of claims joined by overlaps, each in order and listed by
+    /// their first claim. Claims that overlap nothing aren't in any group.
+    pub fn clusters(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.claims.len()];
+        let mut clusters = Vec::new();
+        for start in 0..self.claims.len() {
+            if seen[start] || self.overlaps[start].is_empty() {
+                continue;
+            }
+
+            seen[start] = true;
+            let mut cluster = vec![start];
+            let mut next = 0;
+            while next < cluster.len() {
+                for overlap in &self.overlaps[cluster[next]] {
+                    if !seen[overlap.claim] {
+                        seen[overlap.claim] = true;
+                        cluster.push(overlap.claim);
+                    }
+                }
+                next += 1;
+            }
+
+            cluster.sort_unstable();
+            clusters.push(cluster);
+        }
+        clusters
+    }
+
+    /// The claim overlapping the most other claims, the earliest if more
+    /// than one do, unless no claims overlap at all.
+    pub fn most_conflicted(&self) -> Option<usize> {
+        let mut most = None;
+        for (index, overlaps) in self.overlaps.iter().enumerate() {
+            let conflicts = overlaps.len();
+            if conflicts > 0 && most.is_none_or(|(_, most_conflicts)| conflicts > most_conflicts) {
+                most = Some((index, conflicts));
+            }
+        }
+        most.map(|(index, _)| index)
+    }
+
+    /// How much of the claim at `index` no other claim covers, or an error
+    /// if the area the other claims cover is too big to work out.
+    pub fn exclusive_area(&self, index: usize) -> Result<i128, Error> {
+        let claim = &self.claims[index];
+        let shared: Vec<Claim> = self.overlaps[index].iter()
+            .map(|overlap| claim.intersection(&self.claims[overlap.claim]).unwrap())
+            .collect();
+        let covered = sweep::covered_area(&shared)
+            .ok_or_else(|| Error::Invalid(String::from("the covered area is too big to work out")))?;
+        Ok(claim.area() - covered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims_from;
+
+    const CLAIMS: &str = "\
+#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+#4 @ 10,10: 3x3
+#5 @ 12,12: 2x2
+#6 @ 2,2: 2x2
+";
+
+    #[test]
+    fn test_overlaps() {
+        let claims = claims_from(CLAIMS).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        assert_eq!(&[Overlap { claim: 1, area: 4 }, Overlap { claim: 5, area: 2 }], graph.overlaps(0));
+        assert_eq!(&[Overlap { claim: 0, area: 4 }, Overlap { claim: 5, area: 2 }], graph.overlaps(1));
+        assert!(graph.overlaps(2).is_empty());
+        assert_eq!(&[Overlap { claim: 4, area: 1 }], graph.overlaps(3));
+        assert_eq!(Some(4), graph.find(5));
+        assert_eq!(None, graph.find(7));
+    }
+
+    #[test]
+    fn test_clusters() {
+        let claims = claims_from(CLAIMS).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        assert_eq!(vec![vec![0, 1, 5], vec![3, 4]], graph.clusters());
+    }
+
+    #[test]
+    fn test_most_conflicted() {
+        let claims = claims_from(CLAIMS).unwrap();
+        assert_eq!(Some(0), OverlapGraph::new(&claims).most_conflicted());
+
+        let claims = claims_from("#1 @ 0,0: 1x1\n#2 @ 5,5: 1x1\n").unwrap();
+        assert_eq!(None, OverlapGraph::new(&claims).most_conflicted());
+    }
+
+    #[test]
+    fn test_exclusive_area() {
+        let claims = claims_from(CLAIMS).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        // Claim 1 shares a 2x2 square with claim 2 and a corner of it with
+        // claim 6 as well, and another square only with claim 6
+        assert_eq!(Ok(11), graph.exclusive_area(0));
+        assert_eq!(Ok(4), graph.exclusive_area(2));
+        assert_eq!(Ok(1), graph.exclusive_area(5));
+    }
+
+    #[test]
+    fn test_exclusive_area_huge_claims() {
+        // Each quarter of the whole fabric claimed twice, so every claim is
+        // shared in full with its twin
+        let max = i64::MAX;
+        let claims = claims_from(&format!("\
+#1 @ 0,0: {m}x{m}
+#2 @ 0,0: {m}x{m}
+#3 @ -{m},0: {m}x{m}
+#4 @ -{m},0: {m}x{m}
+#5 @ -{m},-{m}: {m}x{m}
+#6 @ -{m},-{m}: {m}x{m}
+#7 @ 0,-{m}: {m}x{m}
+#8 @ 0,-{m}: {m}x{m}
+", m = max)).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        for index in 0..claims.len() {
+            assert_eq!(Ok(0), graph.exclusive_area(index));
+        }
+    }
+}
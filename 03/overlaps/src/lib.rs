@@ -0,0 +1,282 @@
+extern crate aoc_common;
+extern crate regex;
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+use regex::Regex;
+use std::sync::LazyLock;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+pub mod boxes;
+pub mod graph;
+pub mod sweep;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process_lines(input.to_string())?.into())
+    }
+}
+
+pub fn process_lines(input: String) -> Result<i64, Error> {
+    let claims = claims_from(&input)?;
+    let area = sweep::overlapped_area(&claims)
+        .ok_or_else(|| Error::Invalid(String::from("the overlapped area is too big to work out")))?;
+    i64::try_from(area).map_err(|_| Error::Invalid(format!("the overlapped area {} is too big to give as an answer", area)))
+}
+
+/// Parses one claim per line of `lines`.
+pub fn claims_from(lines: &str) -> Result<Vec<Claim>, Error> {
+    aoc_common::parse_lines(lines)
+}
+
+/// A rectangle of fabric an elf would like to use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Claim {
+    pub claim_id: i32,
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+    pub right: i64,
+    pub bottom: i64,
+}
+
+/// A claim, like `#1 @ 1,3: 4x4`.
+static CLAIM_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^#(?P<id>[^ ]*) @ (?P<x>[^,]*),(?P<y>[^:]*): (?P<width>[^x]*)x(?P<height>.*)$").unwrap());
+
+impl FromStr for Claim {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Claim, ParseError> {
+        let captures = match CLAIM_RE.captures(string) {
+            Some(captures) => captures,
+            None => return Err(ParseError::new(1, string, "expected a claim like '#1 @ 1,3: 4x4'")),
+        };
+
+        let field = |name: &str| captures.name(name).unwrap().as_str();
+        let edge = |name: &str, expected: &str| aoc_common::parse_field::<i64>(string, field(name), expected);
+        let claim_id = aoc_common::parse_field(string, field("id"), "a claim id")?;
+        let x = edge("x", "a left edge")?;
+        let y = edge("y", "a top edge")?;
+        let width = edge("width", "a width")?;
+        let height = edge("height", "a height")?;
+
+        Claim::new(claim_id, x, y, width, height)
+            .ok_or_else(|| ParseError::new(1, string, "expected a claim with its far edges in range"))
+    }
+}
+
+impl fmt::Display for Claim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{} @ {},{}: {}x{}", self.claim_id, self.x, self.y, self.width, self.height)
+    }
+}
+
+impl Claim {
+    /// A claim, unless its far edges are too far out to work with.
+    pub fn new(claim_id: i32, x: i64, y: i64, width: i64, height: i64) -> Option<Claim> {
+        let right = x.checked_add(width)?;
+        let bottom = y.checked_add(height)?;
+        Some(Claim { claim_id, x, y, width, height, right, bottom })
+    }
+
+    pub fn contains_point(&self, x: i64, y: i64) -> bool {
+        x >= self.x && x < self.right && y >= self.y && y < self.bottom
+    }
+
+    /// How much fabric the claim covers.
+    pub fn area(&self) -> i128 {
+        if self.width <= 0 || self.height <= 0 {
+            return 0;
+        }
+        self.width as i128 * self.height as i128
+    }
+
+    /// The fabric both claims cover, as a claim with this one's id, if they
+    /// overlap at all.
+    pub fn intersection(&self, other: &Claim) -> Option<Claim> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right.min(other.right);
+        let bottom = self.bottom.min(other.bottom);
+        if right <= x || bottom <= y || self.area() == 0 || other.area() == 0 {
+            return None;
+        }
+
+        Some(Claim { claim_id: self.claim_id, x, y, width: right - x, height: bottom - y, right, bottom })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_claim_from_string() {
+        let input = String::from("#123 @ 4,56: 789x101112");
+        let result = input.parse::<Claim>().unwrap();
+
+        assert_eq!(123, result.claim_id);
+        assert_eq!(4, result.x);
+        assert_eq!(56, result.y);
+        assert_eq!(789, result.width);
+        assert_eq!(101_112, result.height);
+        assert_eq!(793, result.right);
+        assert_eq!(101_168, result.bottom);
+    }
+
+    #[test]
+    fn test_parsing_claim_from_string2() {
+        let input = String::from("#1 @ 1,3: 4x4");
+        let result = input.parse::<Claim>().unwrap();
+
+        assert_eq!(1, result.claim_id);
+        assert_eq!(1, result.x);
+        assert_eq!(3, result.y);
+        assert_eq!(4, result.width);
+        assert_eq!(4, result.height);
+        assert_eq!(5, result.right);
+        assert_eq!(7, result.bottom);
+    }
+
+    #[test]
+    fn test_parsing() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n");
+        let result = claims_from(&input).unwrap();
+
+        assert_eq!(3, result.len());
+
+        assert_eq!(1, result[0].claim_id);
+        assert_eq!(1, result[0].x);
+        assert_eq!(3, result[0].y);
+        assert_eq!(4, result[0].width);
+        assert_eq!(4, result[0].height);
+        assert_eq!(5, result[0].right);
+        assert_eq!(7, result[0].bottom);
+
+        assert_eq!(2, result[1].claim_id);
+        assert_eq!(3, result[1].x);
+        assert_eq!(1, result[1].y);
+        assert_eq!(4, result[1].width);
+        assert_eq!(4, result[1].height);
+        assert_eq!(7, result[1].right);
+        assert_eq!(5, result[1].bottom);
+
+        assert_eq!(3, result[2].claim_id);
+        assert_eq!(5, result[2].x);
+        assert_eq!(5, result[2].y);
+        assert_eq!(2, result[2].width);
+        assert_eq!(2, result[2].height);
+        assert_eq!(7, result[2].right);
+        assert_eq!(7, result[2].bottom);
+    }
+
+    #[test]
+    fn test_contains_point() {
+        let input = String::from("#1 @ 1,1: 2x2");
+        let claim = input.parse::<Claim>().unwrap();
+
+        assert!(!claim.contains_point(0, 0));
+        assert!(!claim.contains_point(1, 0));
+        assert!(!claim.contains_point(2, 0));
+        assert!(!claim.contains_point(3, 0));
+
+        assert!(!claim.contains_point(0, 1));
+        assert!(claim.contains_point(1, 1));
+        assert!(claim.contains_point(2, 1));
+        assert!(!claim.contains_point(3, 1));
+
+        assert!(!claim.contains_point(0, 2));
+        assert!(claim.contains_point(1, 2));
+        assert!(claim.contains_point(2, 2));
+        assert!(!claim.contains_point(3, 2));
+
+        assert!(!claim.contains_point(0, 3));
+        assert!(!claim.contains_point(1, 3));
+        assert!(!claim.contains_point(2, 3));
+        assert!(!claim.contains_point(3, 3));
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "#123 @ -4,56: 789x101112";
+        assert_eq!(input, input.parse::<Claim>().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_intersection() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 2,4: 0x2\n").unwrap();
+
+        let overlap = claims[0].intersection(&claims[1]).unwrap();
+        assert_eq!(("#1 @ 3,3: 2x2").parse::<Claim>().unwrap(), overlap);
+        assert_eq!(4, overlap.area());
+
+        assert_eq!(None, claims[0].intersection(&claims[2]));
+        assert_eq!(None, claims[0].intersection(&claims[3]));
+        assert_eq!(0, claims[3].area());
+    }
+
+    #[test]
+    fn test_example() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n");
+        let result = process_lines(input).unwrap();
+
+        assert_eq!(4, result);
+    }
+
+    #[test]
+    fn test_parsing_reports_bad_claims() {
+        let input = String::from("#1 @ 1,3: 4x4\n#2 @ 3;1: 4x4\n#3 @ 5,5: 2xtwo\n");
+        let result = claims_from(&input);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(1, "#2 @ 3;1: 4x4", "expected a claim like '#1 @ 1,3: 4x4'").at_line(2),
+            ParseError::new(13, "two", "expected a height").at_line(3),
+        ]);
+        assert_eq!(Err(expected), result.map(|claims| claims.len()));
+    }
+
+    #[test]
+    fn test_claims_past_the_old_fabric() {
+        let input = String::from("#1 @ 999,999: 4x4\n#2 @ 1001,1001: 5x5\n#3 @ -3,0: 2x2\n#4 @ -2,1: 2x2\n");
+        assert_eq!(5, process_lines(input).unwrap());
+
+        let input = String::from("#1 @ 9223372036854775807,0: 1x1");
+        assert!(claims_from(&input).is_err());
+    }
+
+    #[test]
+    fn test_overlapped_area_too_big() {
+        // Every quadrant of the fabric claimed twice, out to i64::MAX each way
+        let input = String::from("\
+            #1 @ 0,0: 9223372036854775807x9223372036854775807\n\
+            #2 @ 0,0: 9223372036854775807x9223372036854775807\n\
+            #3 @ -9223372036854775807,0: 9223372036854775807x9223372036854775807\n\
+            #4 @ -9223372036854775807,0: 9223372036854775807x9223372036854775807\n\
+            #5 @ -9223372036854775807,-9223372036854775807: 9223372036854775807x9223372036854775807\n\
+            #6 @ -9223372036854775807,-9223372036854775807: 9223372036854775807x9223372036854775807\n\
+            #7 @ 0,-9223372036854775807: 9223372036854775807x9223372036854775807\n\
+            #8 @ 0,-9223372036854775807: 9223372036854775807x9223372036854775807\n");
+        let expected = Error::Invalid(String::from("the overlapped area is too big to work out"));
+        assert_eq!(Err(expected), process_lines(input));
+    }
+}
@@ -0,0 +1,365 @@
+//! Sweeping a line across the fabric from left to right, so that the work
+//! done depends on how many claims there are and how many of them overlap,
+//! rather than on how big the fabric is.
+
+use std::collections::HashSet;
+use Claim;
+
+/// The distinct top and bottom edges of the claims, from top to bottom.
+/// The rows between neighbouring edges are what the trees below are built
+/// over, so a claim covers the rows from the index of its top edge up to
+/// the index of its bottom edge.
+struct Edges {
+    edges: Vec<i64>,
+}
+
+impl Edges {
+    fn new(claims: &[Claim]) -> Edges {
+        let mut edges: Vec<i64> = claims.iter().flat_map(|claim| vec![claim.y, claim.bottom]).collect();
+        edges.sort_unstable();
+        edges.dedup();
+        Edges { edges }
+    }
+
+    fn index(&self, y: i64) -> usize {
+        self.edges.binary_search(&y).unwrap()
+    }
+
+    /// How many rows there are between the edges.
+    fn rows(&self) -> usize {
+        self.edges.len().saturating_sub(1)
+    }
+
+    /// How tall the rows from `from` up to `to` are.
+    fn height(&self, from: usize, to: usize) -> i128 {
+        self.edges[to] as i128 - self.edges[from] as i128
+    }
+}
+
+/// Claims that cover no fabric can't overlap anything.
+fn is_empty(claim: &Claim) -> bool {
+    claim.width <= 0 || claim.height <= 0
+}
+
+/// Where each claim starts and stops as the line sweeps across, in order.
+/// Claims stopping at a column stop before any start there, as claims that
+/// only touch edges don't overlap.
+fn events(claims: &[Claim]) -> Vec<(i64, bool, usize)> {
+    let mut events = Vec::new();
+    for (index, claim) in claims.iter().enumerate() {
+        if !is_empty(claim) {
+            events.push((claim.x, true, index));
+            events.push((claim.right, false, index));
+        }
+    }
+    events.sort_unstable();
+    events
+}
+
+/// A segment tree over the rows, counting how many claims currently cover
+/// each and how much of it is covered at least once and at least twice.
+struct CoverageTree<'a> {
+    edges: &'a Edges,
+    /// How many claims cover the whole of each node's rows, without being
+    /// counted at the node above.
+    count: Vec<u32>,
+    once: Vec<i128>,
+    twice: Vec<i128>,
+}
+
+impl<'a> CoverageTree<'a> {
+    fn new(edges: &'a Edges) -> CoverageTree<'a> {
+        let size = 4 * edges.rows().max(1);
+        CoverageTree { edges, count: vec![0; size], once: vec![0; size], twice: vec![0; size] }
+    }
+
+    /// How much of the fabric under the line is covered at least once.
+    fn covered_once(&self) -> i128 {
+        self.once[1]
+    }
+
+    /// How much of the fabric under the line is covered at least twice.
+    fn covered_twice(&self) -> i128 {
+        self.twice[1]
+    }
+
+    fn add(&mut self, from: usize, to: usize, change: i32) {
+        let rows = self.edges.rows();
+        self.update(1, 0, rows, from, to, change);
+    }
+
+    fn update(&mut self, node: usize, low: usize, high: usize, from: usize, to: usize, change: i32) {
+        if to <= low || high <= from {
+            return;
+        }
+
+        let middle = (low + high) / 2;
+        if from <= low && high <= to {
+            self.count[node] = (self.count[node] as i32 + change) as u32;
+        } else {
+            self.update(2 * node, low, middle, from, to, change);
+            self.update(2 * node + 1, middle, high, from, to, change);
+        }
+
+        let leaf = high - low == 1;
+        let (below_once, below_twice) = if leaf {
+            (0, 0)
+        } else {
+            (self.once[2 * node] + self.once[2 * node + 1], self.twice[2 * node] + self.twice[2 * node + 1])
+        };
+        let full = self.edges.height(low, high);
+        match self.count[node] {
+            0 => {
+                self.once[node] = below_once;
+                self.twice[node] = below_twice;
+            },
+            1 => {
+                self.once[node] = full;
+                self.twice[node] = below_once;
+            },
+            _ => {
+                self.once[node] = full;
+                self.twice[node] = full;
+            },
+        }
+    }
+}
+
+/// A segment tree over the rows holding the claims currently under the
+/// line. Each claim is kept at the nodes that together make up its rows, so
+/// every claim kept at a node whose rows meet some other rows covers part
+/// of them.
+struct ActiveTree {
+    rows: usize,
+    claims: Vec<HashSet<usize>>,
+    /// How many claims are kept at each node and every node below it, so
+    /// that empty branches can be skipped.
+    below: Vec<usize>,
+    /// Which search each claim was last found by, so claims kept at more
+    /// than one node are only found once.
+    found_by: Vec<usize>,
+    searches: usize,
+}
+
+impl ActiveTree {
+    fn new(rows: usize, claims: usize) -> ActiveTree {
+        let size = 4 * rows.max(1);
+        ActiveTree {
+            rows,
+            claims: vec![HashSet::new(); size],
+            below: vec![0; size],
+            found_by: vec![0; claims],
+            searches: 0,
+        }
+    }
+
+    fn insert(&mut self, claim: usize, from: usize, to: usize) {
+        let rows = self.rows;
+        self.update(1, 0, rows, from, to, claim, true);
+    }
+
+    fn remove(&mut self, claim: usize, from: usize, to: usize) {
+        let rows = self.rows;
+        self.update(1, 0, rows, from, to, claim, false);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn update(&mut self, node: usize, low: usize, high: usize, from: usize, to: usize, claim: usize, insert: bool) {
+        if to <= low || high <= from {
+            return;
+        }
+
+        if from <= low && high <= to {
+            if insert {
+                self.claims[node].insert(claim);
+            } else {
+                self.claims[node].remove(&claim);
+            }
+        } else {
+            let middle = (low + high) / 2;
+            self.update(2 * node, low, middle, from, to, claim, insert);
+            self.update(2 * node + 1, middle, high, from, to, claim, insert);
+        }
+
+        if insert {
+            self.below[node] += 1;
+        } else {
+            self.below[node] -= 1;
+        }
+    }
+
+    /// Every claim covering any of the rows from `from` up to `to`.
+    fn covering(&mut self, from: usize, to: usize) -> Vec<usize> {
+        self.searches += 1;
+        let mut found = Vec::new();
+        let rows = self.rows;
+        self.search(1, 0, rows, from, to, &mut found);
+        found
+    }
+
+    fn search(&mut self, node: usize, low: usize, high: usize, from: usize, to: usize, found: &mut Vec<usize>) {
+        if to <= low || high <= from || self.below[node] == 0 {
+            return;
+        }
+
+        for claim in &self.claims[node] {
+            if self.found_by[*claim] != self.searches {
+                self.found_by[*claim] = self.searches;
+                found.push(*claim);
+            }
+        }
+
+        if high - low > 1 {
+            let middle = (low + high) / 2;
+            self.search(2 * node, low, middle, from, to, found);
+            self.search(2 * node + 1, middle, high, from, to, found);
+        }
+    }
+}
+
+/// The area of fabric covered by any claim, in O(n log n) for n claims, or
+/// `None` if it's too big for an `i128`.
+pub fn covered_area(claims: &[Claim]) -> Option<i128> {
+    sweep_area(claims, |tree| tree.covered_once())
+}
+
+/// The area of fabric covered by two or more claims, in O(n log n) for n
+/// claims, or `None` if it's too big for an `i128`.
+pub fn overlapped_area(claims: &[Claim]) -> Option<i128> {
+    sweep_area(claims, |tree| tree.covered_twice())
+}
+
+/// Adds up the area `covered` measures under the line between each place
+/// the line stops. Claims reaching across the whole of the fabric can cover
+/// more than an `i128` holds, so gives `None` then.
+fn sweep_area(claims: &[Claim], covered: fn(&CoverageTree) -> i128) -> Option<i128> {
+    let edges = Edges::new(claims);
+    let mut tree = CoverageTree::new(&edges);
+
+    let mut area: i128 = 0;
+    let mut last_x = None;
+    for (x, starts, index) in events(claims) {
+        if let Some(last_x) = last_x {
+            let slice = covered(&tree).checked_mul(x as i128 - last_x as i128)?;
+            area = area.checked_add(slice)?;
+        }
+        last_x = Some(x);
+
+        let claim = &claims[index];
+        let change = if starts { 1 } else { -1 };
+        tree.add(edges.index(claim.y), edges.index(claim.bottom), change);
+    }
+    Some(area)
+}
+
+/// Every pair of claims that overlap, as indexes into `claims` with the
+/// earlier first, in order. Takes O((n + k) log n) for n claims and k
+/// overlapping pairs.
+pub fn overlapping_pairs(claims: &[Claim]) -> Vec<(usize, usize)> {
+    let edges = Edges::new(claims);
+    let mut active = ActiveTree::new(edges.rows(), claims.len());
+
+    let mut pairs = Vec::new();
+    for (_, starts, index) in events(claims) {
+        let claim = &claims[index];
+        let (from, to) = (edges.index(claim.y), edges.index(claim.bottom));
+        if starts {
+            for other in active.covering(from, to) {
+                pairs.push((other.min(index), other.max(index)));
+            }
+            active.insert(index, from, to);
+        } else {
+            active.remove(index, from, to);
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The claims that overlap no other claim, as indexes into `claims`, in
+/// order.
+pub fn uncontested(claims: &[Claim]) -> Vec<usize> {
+    let mut contested = vec![false; claims.len()];
+    for (first, second) in overlapping_pairs(claims) {
+        contested[first] = true;
+        contested[second] = true;
+    }
+
+    (0..claims.len()).filter(|index| !contested[*index]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims_from;
+
+    #[test]
+    fn test_example() {
+        let claims = claims_from("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+        assert_eq!(Some(4), overlapped_area(&claims));
+        assert_eq!(Some(32), covered_area(&claims));
+        assert_eq!(vec![(0, 1)], overlapping_pairs(&claims));
+        assert_eq!(vec![2], uncontested(&claims));
+    }
+
+    #[test]
+    fn test_touching_claims_dont_overlap() {
+        let claims = claims_from("#1 @ 0,0: 2x2\n#2 @ 2,0: 2x2\n#3 @ 0,2: 2x2\n#4 @ 0,0: 0x5\n").unwrap();
+        assert_eq!(Some(0), overlapped_area(&claims));
+        assert_eq!(vec![0, 1, 2, 3], uncontested(&claims));
+    }
+
+    #[test]
+    fn test_many_layers() {
+        // Three claims on top of each other still only overlap once
+        let claims = claims_from("#1 @ 0,0: 4x4\n#2 @ 1,1: 2x2\n#3 @ 1,1: 2x2\n#4 @ 3,3: 5x5\n").unwrap();
+        assert_eq!(Some(5), overlapped_area(&claims));
+        assert_eq!(Some(40), covered_area(&claims));
+        assert_eq!(vec![(0, 1), (0, 2), (0, 3), (1, 2)], overlapping_pairs(&claims));
+        assert!(uncontested(&claims).is_empty());
+    }
+
+    #[test]
+    fn test_huge_coordinates() {
+        let claims = claims_from("#1 @ 0,0: 3000000000x3000000000\n#2 @ 2000000000,-1000000000: 2000000000x2000000000\n").unwrap();
+        assert_eq!(Some(1_000_000_000_000_000_000), overlapped_area(&claims));
+        assert_eq!(vec![(0, 1)], overlapping_pairs(&claims));
+
+        let claims = claims_from("#1 @ -9000000000000000000,0: 9000000000000000000x1\n#2 @ 0,0: 9000000000000000000x1\n").unwrap();
+        assert_eq!(Some(0), overlapped_area(&claims));
+    }
+
+    #[test]
+    fn test_too_big_for_i128() {
+        // Two quadrants of the whole fabric, each claimed twice, only just fit
+        let side = i64::MAX as i128;
+        let claims = claims_from(&quadrants(2)).unwrap();
+        assert_eq!(Some(2 * side * side), overlapped_area(&claims));
+        assert_eq!(Some(2 * side * side), covered_area(&claims));
+
+        // All four don't
+        let claims = claims_from(&quadrants(4)).unwrap();
+        assert_eq!(None, overlapped_area(&claims));
+        assert_eq!(None, covered_area(&claims));
+    }
+
+    /// Claims covering the first `count` quadrants of the whole fabric
+    /// twice over, out to i64::MAX in each direction.
+    fn quadrants(count: usize) -> String {
+        let corners = [(0, 0), (-i64::MAX, 0), (-i64::MAX, -i64::MAX), (0, -i64::MAX)];
+        let mut claims = String::new();
+        for (index, (x, y)) in corners.iter().take(count).enumerate() {
+            for copy in 0..2 {
+                claims += &format!("#{} @ {},{}: {}x{}\n", 2 * index + copy + 1, x, y, i64::MAX, i64::MAX);
+            }
+        }
+        claims
+    }
+
+    #[test]
+    fn test_no_claims() {
+        assert_eq!(Some(0), overlapped_area(&[]));
+        assert!(overlapping_pairs(&[]).is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+#![allow(clippy::needless_return)]
+
+extern crate aoc_common;
+extern crate regex;
+extern crate chrono;
+
+use aoc_common::{Answer, Error, Solution};
+use chrono::prelude::*;
+use log::{Mode, SleepPeriod, SleepRecord};
+use std::collections::HashMap;
+
+pub mod log;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process_lines(input)?.into())
+    }
+}
+
+pub fn process_lines(input: &str) -> Result<u32, Error> {
+    let log = log::read_log(input, Mode::Strict)?;
+    return process_sleep_records(log.sleep_records);
+}
+
+/// The answer for the times the guards slept, which must include at least
+/// one.
+pub fn process_sleep_records(sleep_records: Vec<SleepRecord>) -> Result<u32, Error> {
+    if sleep_records.is_empty() {
+        return Err(Error::Invalid(String::from("no guard ever falls asleep")));
+    }
+
+    let guard_durations = sleep_durations_by_guard_from(sleep_records);
+    let sleepist_guard = sleepiest_guard(&guard_durations);
+
+    let mut sleep_by_minute = HashMap::new();
+    for period in &guard_durations[&sleepist_guard] {
+        for minute in period.start.minute()..(period.start.minute() + period.duration.num_minutes() as u32) {
+            let current = sleep_by_minute.entry(minute).or_insert(0);
+            *current += 1;
+        }
+    }
+
+    let mut sleepiest_minute = 0;
+    let mut sleepiest_value = -1;
+    for (minute, value) in sleep_by_minute {
+        if value > sleepiest_value {
+            sleepiest_minute = minute;
+            sleepiest_value = value;
+        }
+    }
+
+    return Ok(sleepiest_minute * (sleepist_guard as u32));
+}
+
+fn sleepiest_guard(guard_durations: &HashMap<i32, Vec<SleepPeriod>>) -> i32 {
+    let mut guard_totals = HashMap::new();
+    for (guard, periods) in guard_durations {
+        for period in periods {
+            let current = guard_totals.entry(guard).or_insert(0);
+            *current += period.duration.num_minutes();
+        }
+    }
+    let mut max_guard = -1;
+    let mut max_duration = -1;
+    for (guard, total) in guard_totals {
+        if total > max_duration {
+            max_guard = *guard;
+            max_duration = total;
+        }
+    }
+
+    return max_guard;
+}
+
+fn sleep_durations_by_guard_from(sleep_records: Vec<SleepRecord>) -> HashMap<i32, Vec<SleepPeriod>> {
+    let mut durations_by_guard = HashMap::new();
+
+    for record in &sleep_records {
+        let durations = durations_by_guard.entry(record.guard).or_insert(Vec::new());
+        durations.push(record.sleep);
+    }
+
+    return durations_by_guard;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_example() {
+        let lines = "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:25] wakes up\n[1518-11-01 00:30] falls asleep\n[1518-11-01 00:55] wakes up\n[1518-11-01 23:58] Guard #99 begins shift\n[1518-11-02 00:40] falls asleep\n[1518-11-02 00:50] wakes up\n[1518-11-03 00:05] Guard #10 begins shift\n[1518-11-03 00:24] falls asleep\n[1518-11-03 00:29] wakes up\n[1518-11-04 00:02] Guard #99 begins shift\n[1518-11-04 00:36] falls asleep\n[1518-11-04 00:46] wakes up\n[1518-11-05 00:03] Guard #99 begins shift\n[1518-11-05 00:45] falls asleep\n[1518-11-05 00:55] wakes up\n";
+        let result = process_lines(lines).unwrap();
+        assert_eq!(240, result);
+    }
+
+    #[test]
+    fn test_sleep_durations_by_guard() {
+        let lines = "[1518-11-01 00:05] falls asleep\n[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:25] wakes up\n[1518-11-01 00:30] falls asleep\n[1518-11-01 00:55] wakes up\n[1518-11-01 23:58] Guard #99 begins shift\n[1518-11-02 00:40] falls asleep\n[1518-11-02 00:50] wakes up\n";
+        let sleep_records = log::read_log(lines, Mode::Strict).unwrap().sleep_records;
+        let result = sleep_durations_by_guard_from(sleep_records);
+
+        assert_eq!(2, result.len());
+
+        assert_eq!(2, result[&10].len());
+        assert_eq!(Utc.with_ymd_and_hms(1518, 11, 1, 0, 5, 0).unwrap(), result[&10][0].start);
+        assert_eq!(Duration::minutes(20), result[&10][0].duration);
+        assert_eq!(Utc.with_ymd_and_hms(1518, 11, 1, 0, 30, 0).unwrap(), result[&10][1].start);
+        assert_eq!(Duration::minutes(25), result[&10][1].duration);
+
+        assert_eq!(1, result[&99].len());
+        assert_eq!(Utc.with_ymd_and_hms(1518, 11, 2, 0, 40, 0).unwrap(), result[&99][0].start);
+        assert_eq!(Duration::minutes(10), result[&99][0].duration);
+    }
+
+    #[test]
+    fn test_sleepiest_guard() {
+        let lines = "[1518-11-01 00:05] falls asleep\n[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:25] wakes up\n[1518-11-01 00:30] falls asleep\n[1518-11-01 00:55] wakes up\n[1518-11-01 23:58] Guard #99 begins shift\n[1518-11-02 00:40] falls asleep\n[1518-11-02 00:50] wakes up\n";
+        let sleep_records = log::read_log(lines, Mode::Strict).unwrap().sleep_records;
+        let guard_durations = sleep_durations_by_guard_from(sleep_records);
+        let result = sleepiest_guard(&guard_durations);
+
+        assert_eq!(10, result);
+    }
+
+    #[test]
+    fn test_no_sleep() {
+        let lines = "[1518-11-01 00:00] Guard #10 begins shift\n";
+        assert_eq!(Err(Error::Invalid(String::from("no guard ever falls asleep"))), process_lines(lines));
+    }
+}
@@ -0,0 +1,406 @@
+//! Reading the guards' log, checking that its entries make sense together.
+//! A strict read reports every entry that doesn't, while a lenient one
+//! repairs what it can and reports what it repaired.
+
+use aoc_common::{Error, ParseError};
+use chrono::prelude::*;
+use chrono::Duration;
+use regex::Regex;
+use std::env;
+use std::process;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// What the day 4 binaries say about how they read the log.
+const USAGE: &str = "\
+The log must make sense: guards fall asleep and wake up in turn, and only
+while on shift. --lenient repairs a log that doesn't, ignoring entries it
+can't use and waking guards left asleep by the end of the midnight hour,
+and lists the repairs it made.";
+
+/// How to treat entries that don't make sense, like a guard waking up who
+/// never fell asleep.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mode {
+    /// Reports every such entry as an error.
+    Strict,
+    /// Repairs the log around them and carries on.
+    Lenient,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SleepPeriod {
+    pub start: DateTime<Utc>,
+    pub duration: Duration,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SleepRecord {
+    pub guard: i32,
+    pub sleep: SleepPeriod,
+}
+
+/// Every time a guard slept, in order, and the problems with the log that
+/// were repaired to find them.
+#[derive(Debug)]
+pub struct GuardLog {
+    pub sleep_records: Vec<SleepRecord>,
+    pub repairs: Vec<ParseError>,
+}
+
+/// Reads the log in `text`, whose entries can be in any order.
+///
+/// Leniently, falling asleep with no guard on shift or while already asleep
+/// is ignored, as is waking up while awake. A guard still asleep when the
+/// shift changes or the log ends wakes at the end of the midnight hour, or
+/// at the change if that's sooner.
+pub fn read_log(text: &str, mode: Mode) -> Result<GuardLog, Error> {
+    let records = records_from_lines(text)?;
+    let (sleep_records, mut problems) = sleep_records_from(&records);
+    problems.sort_by_key(|problem| problem.line);
+
+    if mode == Mode::Strict && !problems.is_empty() {
+        return Err(Error::Parse(problems));
+    }
+
+    return Ok(GuardLog { sleep_records, repairs: problems });
+}
+
+#[derive(Debug,PartialEq)]
+enum GuardState {
+    Begin(i32),
+    Awake,
+    Asleep,
+}
+
+/// The start of a guard's shift, like `Guard #10 begins shift`.
+static GUARD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^Guard #(?P<id>\S*) begins shift$").unwrap());
+
+impl FromStr for GuardState {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<GuardState, ParseError> {
+        let guard_captures = GUARD_RE.captures(string);
+        if let Some(guard_captures) = guard_captures {
+            let id = guard_captures.name("id").unwrap().as_str();
+            return Ok(GuardState::Begin(aoc_common::parse_field(string, id, "a guard id")?));
+        } else {
+            if string == "falls asleep" {
+                return Ok(GuardState::Asleep);
+            } else if string == "wakes up" {
+                return Ok(GuardState::Awake);
+            } else {
+                return Err(ParseError::new(1, string, "expected 'Guard #<id> begins shift', 'falls asleep' or 'wakes up'"));
+            }
+        }
+    }
+}
+
+/// An entry in the log, and where in the input it was.
+#[derive(Debug)]
+struct Record {
+    moment: DateTime<Utc>,
+    state: GuardState,
+    line: usize,
+    column: usize,
+    entry: String,
+}
+
+impl Record {
+    /// A problem with this entry, described by `reason`.
+    fn problem(&self, reason: &str) -> ParseError {
+        ParseError::new(self.column, &self.entry, reason).at_line(self.line)
+    }
+}
+
+/// An entry in the log, like `[1518-11-01 00:05] falls asleep`.
+static ENTRY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\[(?P<date>[^\]]*)\] (?P<entry>.*)$").unwrap());
+
+fn split_entry(entry_string: &str) -> Result<(DateTime<Utc>, GuardState), ParseError> {
+    let captures = match ENTRY_RE.captures(entry_string) {
+        Some(captures) => captures,
+        None => return Err(ParseError::new(1, entry_string, "expected an entry like '[1518-11-01 00:00] falls asleep'")),
+    };
+
+    let date = captures.name("date").unwrap();
+    let entry = captures.name("entry").unwrap();
+    let date = date_from(date.as_str())
+        .map_err(|error| error.offset_by(date.start()))?;
+    let state = entry.as_str().parse::<GuardState>()
+        .map_err(|error| error.offset_by(entry.start()))?;
+    Ok((date, state))
+}
+
+fn date_from(string: &str) -> Result<DateTime<Utc>, ParseError> {
+    NaiveDateTime::parse_from_str(string, "%Y-%m-%d %H:%M")
+        .map(|date| date.and_utc())
+        .map_err(|_| ParseError::new(1, string, "expected a date like '1518-11-01 00:00'"))
+}
+
+/// The entries of the log in the order they happened. Entries at the same
+/// moment stay in the order they were written.
+fn records_from_lines(text: &str) -> Result<Vec<Record>, Error> {
+    // Lines reach the parser trimmed, so columns need their indent back
+    let indents: Vec<usize> = text.lines().map(|line| line.len() - line.trim_start().len()).collect();
+    let mut records = aoc_common::parse_numbered_lines_with(text, |line, string| {
+        let (moment, state) = split_entry(string)?;
+        let entry = &string[(string.find(']').unwrap() + 2)..];
+        let column = indents[line - 1] + string.len() - entry.len() + 1;
+        Ok(Record { moment, state, line, column, entry: entry.to_string() })
+    })?;
+    records.sort_by( |a, b| { a.moment.cmp(&b.moment) });
+
+    return Ok(records);
+}
+
+/// Works out when each guard slept from the entries of the log, repairing
+/// any that don't make sense as `read_log` describes and returning what
+/// was wrong with them.
+fn sleep_records_from(records: &[Record]) -> (Vec<SleepRecord>, Vec<ParseError>) {
+    let mut sleep_records = Vec::new();
+    let mut problems = Vec::new();
+
+    let mut current_guard = None;
+    let mut sleep_start: Option<&Record> = None;
+    for record in records {
+        match (&record.state, current_guard) {
+            (GuardState::Begin(guard_id), _) => {
+                if let (Some(start), Some(guard)) = (sleep_start, current_guard) {
+                    problems.push(start.problem(&format!("expected guard #{} to wake up before the shift changes on line {}", guard, record.line)));
+                    let end = end_of_hour(start.moment).min(record.moment);
+                    sleep_records.push(SleepRecord { guard, sleep: SleepPeriod { start: start.moment, duration: end.signed_duration_since(start.moment) } });
+                }
+                current_guard = Some(*guard_id);
+                sleep_start = None;
+            },
+            (GuardState::Asleep, None) => {
+                problems.push(record.problem("expected a guard to begin a shift before anyone falls asleep"));
+            },
+            (GuardState::Asleep, Some(guard)) => {
+                match sleep_start {
+                    None => sleep_start = Some(record),
+                    Some(start) => problems.push(record.problem(&format!("expected guard #{}, asleep since line {}, to wake up before falling asleep again", guard, start.line))),
+                }
+            },
+            (GuardState::Awake, _) => {
+                match (sleep_start, current_guard) {
+                    (Some(start), Some(guard)) => {
+                        let duration = record.moment.signed_duration_since(start.moment);
+                        sleep_records.push(SleepRecord { guard, sleep: SleepPeriod { start: start.moment, duration } });
+                        sleep_start = None;
+                    },
+                    _ => problems.push(record.problem("expected a guard to fall asleep before waking up")),
+                }
+            },
+        }
+    }
+
+    if let (Some(start), Some(guard)) = (sleep_start, current_guard) {
+        problems.push(start.problem(&format!("expected guard #{} to wake up before the log ends", guard)));
+        let duration = end_of_hour(start.moment).signed_duration_since(start.moment);
+        sleep_records.push(SleepRecord { guard, sleep: SleepPeriod { start: start.moment, duration } });
+    }
+
+    return (sleep_records, problems);
+}
+
+/// The end of the hour `moment` is in, when a guard who never woke up is
+/// taken to have, as guards only sleep in the midnight hour.
+fn end_of_hour(moment: DateTime<Utc>) -> DateTime<Utc> {
+    moment.with_minute(0).unwrap() + Duration::hours(1)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub mode: Mode,
+    pub input: Option<String>,
+}
+
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options { mode: Mode::Strict, input: None };
+
+    for arg in args {
+        match arg.as_str() {
+            "--lenient" => options.mode = Mode::Lenient,
+            _ if options.input.is_none() && !arg.starts_with("--") => options.input = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Reads the log named on the command line of the binary `name`, which
+/// prints `description`, listing any repairs made to it. Exits if the
+/// command line or the log is wrong.
+pub fn read_log_from_args(name: &str, description: &str) -> Vec<SleepRecord> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\nUsage: {} [--lenient] [input]\n\n{}\n\n{}", message, name, description, USAGE);
+            process::exit(2);
+        }
+    };
+    let input = aoc_common::read_input_from(options.input.as_deref());
+
+    let log = aoc_common::unwrap_or_exit(read_log(&input, options.mode));
+    if !log.repairs.is_empty() {
+        eprintln!("Repaired the log:");
+        for repair in &log.repairs {
+            eprintln!("    {}", repair);
+        }
+    }
+
+    return log.sleep_records;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[1518-11-01 00:05] falls asleep\n[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:25] wakes up\n[1518-11-01 00:30] falls asleep\n[1518-11-01 00:55] wakes up\n[1518-11-01 23:58] Guard #99 begins shift\n[1518-11-02 00:40] falls asleep\n[1518-11-02 00:50] wakes up\n";
+
+    fn sleep(guard: i32, day: u32, minute: u32, minutes: i64) -> SleepRecord {
+        let start = Utc.with_ymd_and_hms(1518, 11, day, 0, minute, 0).unwrap();
+        SleepRecord { guard, sleep: SleepPeriod { start, duration: Duration::minutes(minutes) } }
+    }
+
+    #[test]
+    fn test_sleep_durations() {
+        let result = read_log(EXAMPLE, Mode::Strict).unwrap();
+
+        assert_eq!(vec![sleep(10, 1, 5, 20), sleep(10, 1, 30, 25), sleep(99, 2, 40, 10)], result.sleep_records);
+        assert_eq!(0, result.repairs.len());
+    }
+
+    #[test]
+    fn test_lines_to_records() {
+        let result = records_from_lines(EXAMPLE).unwrap();
+
+        assert_eq!(8, result.len());
+        let lines: Vec<usize> = result.iter().map(|record| record.line).collect();
+        assert_eq!(vec![2, 1, 3, 4, 5, 6, 7, 8], lines);
+        assert_eq!(GuardState::Begin(10), result[0].state);
+        assert_eq!(GuardState::Asleep, result[1].state);
+        assert_eq!(20, result[1].column);
+        assert_eq!("falls asleep", result[1].entry);
+    }
+
+    #[test]
+    fn test_indented_line_columns() {
+        let lines = "[1518-11-01 00:00] Guard #10 begins shift\n  [1518-11-01 00:05] falls asleep\n";
+        let result = read_log(lines, Mode::Strict);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(22, "falls asleep", "expected guard #10 to wake up before the log ends").at_line(2),
+        ]);
+        assert_eq!(Err(expected), result.map(|log| log.sleep_records));
+    }
+
+    #[test]
+    fn test_strict_reports_every_problem() {
+        let lines = "[1518-11-01 00:01] falls asleep\n[1518-11-01 00:02] wakes up\n[1518-11-01 00:03] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:07] falls asleep\n[1518-11-01 00:20] wakes up\n[1518-11-01 00:21] wakes up\n[1518-11-01 00:30] falls asleep\n[1518-11-02 00:00] Guard #99 begins shift\n[1518-11-02 00:40] falls asleep\n";
+        let result = read_log(lines, Mode::Strict);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(20, "falls asleep", "expected a guard to begin a shift before anyone falls asleep").at_line(1),
+            ParseError::new(20, "wakes up", "expected a guard to fall asleep before waking up").at_line(2),
+            ParseError::new(20, "falls asleep", "expected guard #10, asleep since line 4, to wake up before falling asleep again").at_line(5),
+            ParseError::new(20, "wakes up", "expected a guard to fall asleep before waking up").at_line(7),
+            ParseError::new(20, "falls asleep", "expected guard #10 to wake up before the shift changes on line 9").at_line(8),
+            ParseError::new(20, "falls asleep", "expected guard #99 to wake up before the log ends").at_line(10),
+        ]);
+        assert_eq!(Err(expected), result.map(|log| log.sleep_records));
+    }
+
+    #[test]
+    fn test_lenient_repairs() {
+        let lines = "[1518-11-01 00:01] falls asleep\n[1518-11-01 00:03] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n[1518-11-01 00:07] falls asleep\n[1518-11-01 00:20] wakes up\n[1518-11-01 00:21] wakes up\n[1518-11-01 00:30] falls asleep\n[1518-11-01 00:45] Guard #99 begins shift\n[1518-11-02 00:40] falls asleep\n";
+        let result = read_log(lines, Mode::Lenient).unwrap();
+
+        assert_eq!(vec![sleep(10, 1, 5, 15), sleep(10, 1, 30, 15), sleep(99, 2, 40, 20)], result.sleep_records);
+        let repaired: Vec<usize> = result.repairs.iter().map(|repair| repair.line).collect();
+        assert_eq!(vec![1, 4, 6, 7, 9], repaired);
+    }
+
+    #[test]
+    fn test_lenient_wakes_sleepers_by_the_end_of_the_hour() {
+        let lines = "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:50] falls asleep\n[1518-11-01 23:58] Guard #99 begins shift\n";
+        let result = read_log(lines, Mode::Lenient).unwrap();
+
+        assert_eq!(vec![sleep(10, 1, 50, 10)], result.sleep_records);
+        assert_eq!(1, result.repairs.len());
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(Ok(Options { mode: Mode::Strict, input: None }), parse_args(&args(&[])));
+        assert_eq!(Ok(Options { mode: Mode::Lenient, input: Some(String::from("-")) }), parse_args(&args(&["--lenient", "-"])));
+
+        assert!(parse_args(&args(&["a.txt", "b.txt"])).is_err());
+        assert_eq!(Err(String::from("Unexpected argument '--lenent'")), parse_args(&args(&["--lenent"])));
+    }
+
+    #[test]
+    fn test_split_entry() {
+        let (date, state) = split_entry("[1518-11-01 00:00] Guard #10 begins shift").unwrap();
+        assert_eq!(Utc.with_ymd_and_hms(1518, 11, 1, 0, 0, 0).unwrap(), date);
+        assert_eq!(GuardState::Begin(10), state);
+
+        let (date, state) = split_entry("[1518-11-01 01:02] falls asleep").unwrap();
+        assert_eq!(Utc.with_ymd_and_hms(1518, 11, 1, 1, 2, 0).unwrap(), date);
+        assert_eq!(GuardState::Asleep, state);
+
+        let (date, state) = split_entry("[1518-11-01 03:04] wakes up").unwrap();
+        assert_eq!(Utc.with_ymd_and_hms(1518, 11, 1, 3, 4, 0).unwrap(), date);
+        assert_eq!(GuardState::Awake, state);
+    }
+
+    #[test]
+    fn test_guard_state_string_with_id() {
+        let state = "Guard #10 begins shift".parse::<GuardState>().unwrap();
+        assert_eq!(GuardState::Begin(10), state);
+    }
+
+    #[test]
+    fn test_guard_state_string_asleep() {
+        let state = "falls asleep".parse::<GuardState>().unwrap();
+        assert_eq!(GuardState::Asleep, state);
+    }
+
+    #[test]
+    fn test_guard_state_string_awake() {
+        let state = "wakes up".parse::<GuardState>().unwrap();
+        assert_eq!(GuardState::Awake, state);
+    }
+
+    #[test]
+    fn test_date_parsing() {
+        let date = date_from("1518-11-01 00:05").unwrap();
+        assert_eq!(1518, date.year());
+        assert_eq!(11, date.month());
+        assert_eq!(1, date.day());
+
+        assert_eq!(0, date.hour());
+        assert_eq!(5, date.minute());
+        assert_eq!(0, date.second());
+    }
+
+    #[test]
+    fn test_lines_to_records_reports_bad_entries() {
+        let lines = "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] nods off\n[1518-13-01 00:25] wakes up\n1518-11-01 00:30 falls asleep\n";
+        let result = records_from_lines(lines);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(20, "nods off", "expected 'Guard #<id> begins shift', 'falls asleep' or 'wakes up'").at_line(2),
+            ParseError::new(2, "1518-13-01 00:25", "expected a date like '1518-11-01 00:00'").at_line(3),
+            ParseError::new(1, "1518-11-01 00:30 falls asleep", "expected an entry like '[1518-11-01 00:00] falls asleep'").at_line(4),
+        ]);
+        assert_eq!(Err(expected), result.map(|records| records.len()));
+    }
+}
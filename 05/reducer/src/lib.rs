@@ -0,0 +1,151 @@
+#![allow(clippy::needless_return)]
+
+extern crate aoc_common;
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process(input)?.into())
+    }
+}
+
+pub fn process(input: &str) -> Result<usize, Error> {
+    let input = read_polymer(input)?;
+    let output = reduce(input);
+    return Ok(output.len());
+}
+
+/// Reads the polymer, which must be a single line of units, each a letter
+/// from a to z in either case.
+fn read_polymer(input: &str) -> Result<String, Error> {
+    let polymers = aoc_common::parse_lines_with(input, |line| {
+        for (index, unit) in line.char_indices() {
+            if !unit.is_ascii_alphabetic() {
+                let unit = &line[index..index + unit.len_utf8()];
+                return Err(ParseError::within(line, unit, "expected a unit from a to z"));
+            }
+        }
+        Ok(line.to_string())
+    })?;
+
+    if polymers.len() != 1 {
+        return Err(Error::Invalid(format!("expected one polymer, found {}", polymers.len())));
+    }
+
+    return Ok(polymers[0].clone());
+}
+
+fn reduce(string: String) -> String {
+    string.chars().fold(String::new(), |mut acc, c| {
+        match acc.pop() {
+            Some(last) => {
+                if !is_reactive(last, c) {
+                    acc.push(last);
+                    acc.push(c);
+                }
+            },
+            None => acc.push(c),
+        }
+        acc
+    })
+}
+
+fn is_reactive(a: char, b: char) -> bool {
+    assert!(a.is_ascii());
+    assert!(b.is_ascii());
+
+    if !a.eq_ignore_ascii_case(&b) {
+        return false;
+    }
+
+    if (a.is_lowercase() && b.is_uppercase()) || (a.is_uppercase() && b.is_lowercase()) {
+        return true;
+    }
+
+    return false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_reactive() {
+        assert!(is_reactive('a', 'A'));
+        assert!(is_reactive('A', 'a'));
+        assert!(!is_reactive('A', 'A'));
+        assert!(!is_reactive('A', 'b'));
+    }
+
+    #[test]
+    fn test_reduce_to_empty() {
+        let input = String::from("aA");
+        let result = reduce(input);
+        assert_eq!("", result);
+    }
+
+    #[test]
+    fn test_reduce_to_one_from_end() {
+        let input = String::from("aAb");
+        let result = reduce(input);
+        assert_eq!("b", result);
+    }
+
+    #[test]
+    fn test_reduce_to_one_from_start() {
+        let input = String::from("baA");
+        let result = reduce(input);
+        assert_eq!("b", result);
+    }
+
+    #[test]
+    fn test_reduce_to_empty_2_steps() {
+        let input = String::from("baAB");
+        let result = reduce(input);
+        assert_eq!("", result);
+    }
+
+    #[test]
+    fn test_reduce_to_one_3_steps() {
+        let input = String::from("baABB");
+        let result = reduce(input);
+        assert_eq!("B", result);
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "dabAcCaCBAcCcaDA";
+        let result = process(input).unwrap();
+        assert_eq!(10, result);
+    }
+
+    #[test]
+    fn test_read_polymer_reports_bad_units() {
+        let result = read_polymer("dabAcC-aCBAcCcaDA\n");
+        let expected = Error::Parse(vec![
+            ParseError::new(7, "-", "expected a unit from a to z").at_line(1),
+        ]);
+        assert_eq!(Err(expected), result);
+
+        assert!(read_polymer("dabAcC\naCBAcCcaDA\n").is_err());
+    }
+}
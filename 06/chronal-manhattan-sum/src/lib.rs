@@ -0,0 +1,360 @@
+#![allow(clippy::needless_return)]
+
+extern crate aoc_common;
+extern crate regex;
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+use std::cmp;
+use regex::Regex;
+use std::sync::LazyLock;
+use std::str::FromStr;
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process(input, 10_000)?.into())
+    }
+}
+
+pub fn process(input: &str, max_distance: i32) -> Result<i32, Error> {
+    let points = Point::from_lines(input)?;
+
+    // Work out how large a grid we have to consider
+    let (max_x, max_y) = extent(&points);
+
+    // Score a grid for each of the points
+    let mut scored_grids = Vec::new();
+    for point in points {
+        let scored_grid = scored_grid_from(point, max_x, max_y);
+        scored_grids.push(scored_grid);
+    }
+
+    // Add the grids
+    let mut total_grid = Vec::new();
+    let cell_count = max_x * max_y;
+    for cell_index in 0..cell_count {
+        let mut total = 0;
+        for grid in &scored_grids {
+            let grid_cell_score = grid[cell_index];
+            total += grid_cell_score;
+        }
+        total_grid.push(total);
+    }
+
+    let area = total_grid.iter().fold(0, |acc, value| {
+        if *value < max_distance {
+            return acc + 1;
+        } else {
+            return acc;
+        }
+    });
+
+    return Ok(area);
+}
+
+#[cfg(test)]
+fn grid_is_infinite(grid: &[i32], extent_x: usize, extent_y: usize) -> bool {
+    let max = extent_x * extent_y;
+
+    let top_range = 0..extent_x;
+    let bottom_range = (extent_x * (extent_y - 1))..max;
+    for (top, bottom) in top_range.zip(bottom_range) {
+        if grid[top] >= 0 || grid[bottom]  >= 0 {
+            return true;
+        }
+    }
+
+    let left_range = (0 ..max).step_by(extent_x);
+    let right_range = ((extent_x - 1)..max).step_by(extent_x);
+    for (left, right) in left_range.zip(right_range) {
+        if grid[left] >= 0 || grid[right]  >= 0 {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+fn scored_grid_from(point: Point, extent_x: usize, extend_y: usize) -> Vec<i32> {
+    let mut scores = Vec::new();
+    for y in 0..extend_y {
+        for x in 0..extent_x {
+            let score = ((y as i32) - point.y).abs() + ((x as i32) - point.x).abs();
+            scores.push(score);
+        }
+    }
+    return scores;
+}
+
+fn extent(points: &Vec<Point>) -> (usize, usize) {
+    let mut max_x = 0;
+    let mut max_y = 0;
+    for point in points {
+        max_x = cmp::max(max_x, point.x);
+        max_y = cmp::max(max_y, point.y);
+    }
+    max_x += 1;
+    max_y += 1;
+
+    (max_x as usize, max_y as usize)
+}
+
+//fn print_scores_as_grid(scores: &Vec<i32>, extent_x: usize, extent_y: usize) {
+//    println!("----[Grid]----");
+//    for y in 0..extent_y {
+//        for x in 0..extent_x {
+//            let index = (extent_x * y + x) as usize;
+//            print!("{:>4}", scores[index]);
+//        }
+//        println!("");
+//    }
+//    println!("--------------");
+//}
+
+#[derive(Debug,PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+/// A coordinate, like `1, 6`.
+static POINT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?P<x>[^,]*),(?P<y>.*)$").unwrap());
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Point, ParseError> {
+        let captures = match POINT_RE.captures(string) {
+            Some(captures) => captures,
+            None => return Err(ParseError::new(1, string, "expected a coordinate like '1, 6'")),
+        };
+
+        let x = aoc_common::parse_field(string, captures.name("x").unwrap().as_str().trim(), "an x coordinate")?;
+        let y = aoc_common::parse_field(string, captures.name("y").unwrap().as_str().trim(), "a y coordinate")?;
+        return Ok(Point { x, y });
+    }
+}
+
+impl Point {
+    fn from_lines(lines: &str) -> Result<Vec<Point>, Error> {
+        let points: Vec<Point> = aoc_common::parse_lines(lines)?;
+        if points.is_empty() {
+            return Err(Error::Invalid(String::from("expected at least one coordinate")));
+        }
+        return Ok(points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_from_string() {
+        let subject = "1, 2".parse::<Point>().unwrap();
+        assert_eq!(Point { x: 1, y: 2 }, subject);
+
+        let subject = "100 , 25699".parse::<Point>().unwrap();
+        assert_eq!(Point { x: 100, y: 25699 }, subject);
+    }
+
+    #[test]
+    fn test_point_from_lines_trailing_newline() {
+        let subject = Point::from_lines("1, 2\n3, 4\n5, 6\n").unwrap();
+        let expected = vec![Point {x: 1, y: 2},Point {x: 3, y: 4},Point {x: 5, y: 6},];
+        assert_eq!(expected, subject);
+    }
+
+    #[test]
+    fn test_point_from_lines_trailing() {
+        let subject = Point::from_lines("1, 2\n3, 4\n5, 6").unwrap();
+        let expected = vec![Point {x: 1, y: 2},Point {x: 3, y: 4},Point {x: 5, y: 6},];
+        assert_eq!(expected, subject);
+    }
+
+    #[test]
+    fn test_extent() {
+        let input = vec![Point {x: 0, y: 10}, Point {x: 20, y: 0}, Point {x: 19, y: 10},];
+        let (max_x, max_y) = extent(&input);
+        assert_eq!(21, max_x);
+        assert_eq!(11, max_y);
+    }
+
+    #[test]
+    fn test_scored_grid_1() {
+        let in_point = Point { x: 1, y: 1};
+        let extent_x = 3;
+        let extent_y = 3;
+        let result = scored_grid_from(in_point, extent_x, extent_y);
+
+        let expected = vec![
+            2, 1, 2,
+            1, 0, 1,
+            2, 1, 2,
+        ];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_scored_grid_2() {
+        let in_point = Point { x: 1, y: 1};
+        let extent_x = 5;
+        let extent_y = 7;
+        let result = scored_grid_from(in_point, extent_x, extent_y);
+
+        let expected = vec![
+            2, 1, 2, 3, 4,
+            1, 0, 1, 2, 3,
+            2, 1, 2, 3, 4,
+            3, 2, 3, 4, 5,
+            4, 3, 4, 5, 6,
+            5, 4, 5, 6, 7,
+            6, 5, 6, 7, 8,
+        ];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_grid_is_infinite_top() {
+        let extent_x = 3;
+        let extent_y = 3;
+        let grid = vec![
+             0, -1, -1,
+            -1, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+
+        let grid = vec![
+            -1,  0, -1,
+            -1, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+
+        let grid = vec![
+            -1, -1,  0,
+            -1, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_grid_is_infinite_centre() {
+        let extent_x = 3;
+        let extent_y = 3;
+        let grid = vec![
+            -1, -1, -1,
+            -1, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(!result);
+
+        let grid = vec![
+            -1, -1, -1,
+            -1,  0, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(!result);
+    }
+
+    #[test]
+    fn test_grid_is_infinite_bottom() {
+        let extent_x = 3;
+        let extent_y = 3;
+        let grid = vec![
+            -1, -1, -1,
+            -1, -1, -1,
+             0, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+
+        let grid = vec![
+            -1, -1, -1,
+            -1, -1, -1,
+            -1,  0, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+
+        let grid = vec![
+            -1, -1, -1,
+            -1, -1, -1,
+            -1, -1,  0,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_grid_is_infinite_left() {
+        let extent_x = 3;
+        let extent_y = 3;
+        let grid = vec![
+             0, -1, -1,
+            -1, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+
+        let grid = vec![
+            -1, -1, -1,
+             0, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+
+        let grid = vec![
+            -1, -1, -1,
+             0, -1, -1,
+            -1, -1, -1,
+        ];
+        let result = grid_is_infinite(&grid, extent_x, extent_y);
+        assert!(result);
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9";
+        let result = process(input, 32).unwrap();
+        assert_eq!(16, result);
+    }
+
+    #[test]
+    fn test_point_from_lines_reports_bad_points() {
+        let subject = Point::from_lines("1, 2\n3; 4\n5, six\n");
+        let expected = Error::Parse(vec![
+            ParseError::new(1, "3; 4", "expected a coordinate like '1, 6'").at_line(2),
+            ParseError::new(4, "six", "expected a y coordinate").at_line(3),
+        ]);
+        assert_eq!(Err(expected), subject);
+
+        assert!(Point::from_lines("\n").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+use regex::Regex;
+use std::sync::LazyLock;
+use petgraph::prelude::*;
+use petgraph::dot::{Dot, Config};
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process(input)?.into())
+    }
+}
+
+pub fn process(input: &str) -> Result<String, Error> {
+    let instructions = create_instruction_graph(input)?;
+
+    // Get all the possible start steps and use them as starting points
+    let mut available = Vec::new();
+    for ready_step in instructions.externals(Incoming) {
+        available.push(ready_step);
+    }
+
+    // Work out the order to complete the steps in.
+    let mut steps = String::new();
+    let mut complete = HashSet::new();
+    while !available.is_empty() {
+        // Sort the available steps so we process them in order
+        available.sort_by(|a, b| {
+            let a_step = instructions.node_weight(*a).unwrap();
+            let b_step = instructions.node_weight(*b).unwrap();
+            a_step.cmp(b_step)
+        });
+
+        // Work out the current step. The current step must available
+        // and it must have had all it's prerequisites completed so
+        // that it can be worked on
+        let mut next_suitable_step_index = None;
+        for (step_index, step) in available.iter().enumerate() {
+            let mut satisfied = true;
+            for step_prereq in instructions.neighbors_directed(*step, Incoming) {
+                if !complete.contains(&step_prereq) {
+                    satisfied = false;
+                    break;
+                }
+            }
+
+            if satisfied {
+                next_suitable_step_index = Some(step_index);
+                break;
+            }
+        }
+
+        // Remove it from the available steps
+        let current = available.remove(next_suitable_step_index.unwrap());
+
+        // Complete the step and record it
+        complete.insert(current);
+        steps += instructions.node_weight(current).unwrap();
+
+        // Add any new steps now available
+        for neighbor in instructions.neighbors_directed(current, Outgoing) {
+            if !complete.contains(&neighbor) && !available.contains(&neighbor) {
+                available.push(neighbor);
+            }
+        }
+    }
+
+    return Ok(steps);
+}
+
+fn create_instruction_graph(input: &str) -> Result<Graph<String, String, Directed, u32>, Error> {
+    let raw_instructions = parse_raw_instructions(input)?;
+    let mut instructions = Graph::<String, String>::new();
+    let mut node_map = HashMap::new();
+    for raw_instruction in &raw_instructions {
+        let (step, depends_on) = raw_instruction;
+        if !node_map.contains_key(step) {
+            let node = instructions.add_node(step.clone());
+            node_map.insert(step, node);
+        }
+        if !node_map.contains_key(depends_on) {
+            let node = instructions.add_node(depends_on.clone());
+            node_map.insert(depends_on, node);
+        }
+    }
+    let mut edges = Vec::new();
+    for raw_instruction in &raw_instructions {
+        let (step, depends_on) = raw_instruction;
+        let depends_on_node = node_map[depends_on];
+        let step_node = node_map[step];
+        edges.push((depends_on_node, step_node));
+    }
+    instructions.extend_with_edges(&edges);
+
+    return Ok(instructions);
+}
+
+/// The steps in `input` as a Graphviz DOT graph, for seeing how they
+/// depend on each other.
+pub fn dot(input: &str) -> Result<String, Error> {
+    let instructions = create_instruction_graph(input)?;
+    return Ok(format!("{:?}", Dot::with_config(&instructions, &[Config::EdgeNoLabel])));
+}
+
+/// An instruction, like `Step C must be finished before step A can begin.`
+static INSTRUCTION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^Step (?P<previous>[A-Z]) must be finished before step (?P<step>[A-Z]) can begin\.$").unwrap());
+
+fn parse_raw_instruction(instruction: &str) -> Result<(String, String), ParseError> {
+    let captures = match INSTRUCTION_RE.captures(instruction) {
+        Some(captures) => captures,
+        None => return Err(ParseError::new(1, instruction, "expected an instruction like 'Step C must be finished before step A can begin.'")),
+    };
+    return Ok((captures["step"].to_string(), captures["previous"].to_string()));
+}
+
+fn parse_raw_instructions(instruction_text: &str) -> Result<Vec<(String, String)>, Error> {
+    aoc_common::parse_lines_with(instruction_text, parse_raw_instruction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot() {
+        let result = dot("Step C must be finished before step A can begin.\n").unwrap();
+        assert!(result.starts_with("digraph"));
+        assert!(result.contains("->"));
+    }
+
+    #[test]
+    fn test_parse_raw_instruction() {
+        let input = "Step C must be finished before step A can begin.";
+        let (step, previous) = parse_raw_instruction(input).unwrap();
+        assert_eq!("A", step);
+        assert_eq!("C", previous);
+    }
+
+    #[test]
+    fn test_parse_raw_instructions() {
+        let input = "Step C must be finished before step A can begin.\nStep C must be finished before step F can begin.\nStep A must be finished before step B can begin.\nStep A must be finished before step D can begin.\nStep B must be finished before step E can begin.\nStep D must be finished before step E can begin.\nStep F must be finished before step E can begin.\n";
+        let result = parse_raw_instructions(input).unwrap();
+
+        let expected = vec![
+            ("A".to_string(), "C".to_string()),
+            ("F".to_string(), "C".to_string()),
+            ("B".to_string(), "A".to_string()),
+            ("D".to_string(), "A".to_string()),
+            ("E".to_string(), "B".to_string()),
+            ("E".to_string(), "D".to_string()),
+            ("E".to_string(), "F".to_string()),
+        ];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "Step C must be finished before step A can begin.\nStep C must be finished before step F can begin.\nStep A must be finished before step B can begin.\nStep A must be finished before step D can begin.\nStep B must be finished before step E can begin.\nStep D must be finished before step E can begin.\nStep F must be finished before step E can begin.\n";
+        let result = process(input).unwrap();
+        assert_eq!("CABDFE", result);
+    }
+
+    #[test]
+    fn test_parse_raw_instructions_reports_bad_instructions() {
+        let input = "Step C must be finished before step A can begin.\nStep C must finish before step F can begin.\n";
+        let result = parse_raw_instructions(input);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(1, "Step C must finish before step F can begin.", "expected an instruction like 'Step C must be finished before step A can begin.'").at_line(2),
+        ]);
+        assert_eq!(Err(expected), result);
+    }
+}
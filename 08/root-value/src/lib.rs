@@ -0,0 +1,174 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, Solution};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process(input)?.into())
+    }
+}
+
+pub fn process(input: &str) -> Result<u32, Error> {
+    let mut data = input_to_vec(input)?;
+    let node =  make_node(&mut data)?;
+    if !data.is_empty() {
+        return Err(Error::Invalid(format!("{} numbers left over after the root node", data.len())));
+    }
+
+    return Ok(node.value());
+}
+
+fn input_to_vec(input: &str) -> Result<Vec<u32>, Error> {
+    // The numbers are usually all on one line, so report every bad number
+    // rather than just the first on each line
+    let mut vec = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        for field in line.split_whitespace() {
+            match aoc_common::parse_field(line, field, "a number") {
+                Ok(number) => vec.push(number),
+                Err(error) => errors.push(error.at_line(index + 1)),
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::Parse(errors));
+    }
+
+    vec.reverse();
+    Ok(vec)
+}
+
+fn next_number(input: &mut Vec<u32>) -> Result<u32, Error> {
+    input.pop()
+        .ok_or_else(|| Error::Invalid(String::from("the tree ends part way through a node")))
+}
+
+fn make_node(input: &mut Vec<u32>) -> Result<Node, Error> {
+    let num_children = next_number(input)?;
+    let num_metadatum = next_number(input)?;
+
+    let mut children = Vec::new();
+    for _ in 0..num_children {
+        children.push(make_node(input)?);
+    }
+
+    let mut metadata = Vec::new();
+    for _ in 0..num_metadatum {
+        metadata.push(next_number(input)?);
+    }
+
+    Ok(Node {children, metadata})
+}
+
+struct Node {
+    children: Vec<Node>,
+    metadata: Vec<u32>,
+}
+
+impl Node {
+    #[cfg(test)]
+    fn num_all_children(&self) -> usize {
+        let mut num_all_children: usize = 0;
+        for child in &self.children {
+            num_all_children += child.num_all_children();
+        }
+        return num_all_children + self.children.len();
+    }
+
+    #[cfg(test)]
+    fn sum_metadata(&self) -> u32 {
+        let mut sum: u32 = self.metadata.iter().sum();
+
+        for child in &self.children {
+            sum += child.sum_metadata();
+        }
+
+        return sum;
+    }
+
+    fn value(&self) -> u32 {
+        if self.children.is_empty() {
+            return self.metadata.iter().sum();
+        }
+
+        let mut value = 0u32;
+        for data in &self.metadata {
+            if *data == 0 {
+                continue;
+            }
+
+            let index = (*data - 1) as usize;
+            if index >= self.children.len() {
+                continue;
+            }
+
+            value += self.children[index].value();
+        }
+
+        return value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::ParseError;
+
+    #[test]
+    fn test_input_to_vec() {
+        let input = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+        let result = input_to_vec(input).unwrap();
+
+        let mut expected = vec![2, 3, 0, 3, 10, 11, 12, 1, 1, 0, 1, 99, 2, 1, 1, 2,];
+        expected.reverse();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_vec_to_node() {
+        let input = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+        let mut vec = input_to_vec(input).unwrap();
+        let result = make_node(&mut vec).unwrap();
+        assert_eq!(3, result.num_all_children());
+        assert_eq!(138, result.sum_metadata());
+        assert_eq!(66, result.value());
+    }
+
+    #[test]
+    fn test_input_to_vec_reports_bad_numbers() {
+        let input = "2 3 0 3 10 eleven 12 1 1 0 1 -99 2 1 1 2";
+        let result = input_to_vec(input);
+
+        let expected = Error::Parse(vec![
+            ParseError::new(12, "eleven", "expected a number").at_line(1),
+            ParseError::new(30, "-99", "expected a number").at_line(1),
+        ]);
+        assert_eq!(Err(expected), result);
+    }
+
+    #[test]
+    fn test_process_rejects_bad_trees() {
+        assert!(process("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1").is_err());
+        assert!(process("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2 7").is_err());
+    }
+}
@@ -0,0 +1,217 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+use std::collections::VecDeque;
+use regex::Regex;
+use std::sync::LazyLock;
+use std::str::FromStr;
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process(input)?.into())
+    }
+
+    fn accepts_inline_input(&self) -> bool {
+        true
+    }
+}
+
+pub fn process(input: &str) -> Result<usize, Error> {
+    let mut setups: Vec<GameSetup> = aoc_common::parse_lines(input)?;
+    if setups.len() != 1 {
+        return Err(Error::Invalid(format!("expected one game setup, found {}", setups.len())));
+    }
+
+    let setup = setups.remove(0);
+    let mut game = GameState::new(setup);
+
+    game.play();
+
+    let (_, high_score) = game.highest_score();
+
+    return Ok(high_score);
+}
+
+struct GameSetup {
+    num_players: usize,
+    last_marble_score: usize,
+}
+
+/// A game setup, like `9 players; last marble is worth 25 points`.
+static GAME_SETUP_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?P<players>\S*) players; last marble is worth (?P<last_marble_score>\S*) points$").unwrap());
+
+impl FromStr for GameSetup {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<GameSetup, ParseError> {
+        let captures = match GAME_SETUP_RE.captures(string) {
+            Some(captures) => captures,
+            None => return Err(ParseError::new(1, string, "expected a game setup like '9 players; last marble is worth 25 points'")),
+        };
+
+        let players = captures.name("players").unwrap().as_str();
+        let num_players = aoc_common::parse_field(string, players, "a number of players")?;
+        if num_players == 0 {
+            return Err(ParseError::within(string, players, "expected at least one player"));
+        }
+        let last_marble_score: usize = aoc_common::parse_field(string, captures.name("last_marble_score").unwrap().as_str(), "a marble score")?;
+
+        Ok(GameSetup { num_players, last_marble_score: last_marble_score * 100 })
+    }
+}
+
+struct GameState {
+    setup: GameSetup,
+    player_scores: Vec<usize>,
+    placed_marbles: VecDeque<usize>,
+}
+
+impl GameState {
+    fn new(setup: GameSetup) -> GameState {
+        let player_scores = vec![0usize; setup.num_players];
+        let mut placed_marbles = VecDeque::new();
+        placed_marbles.push_front(0);
+
+        GameState { setup, player_scores, placed_marbles }
+    }
+
+    fn play(&mut self) {
+        for marble in 1..= self.setup.last_marble_score {
+            self.place_next_marble(marble);
+        }
+    }
+
+    fn place_next_marble(&mut self, next_marble_score: usize) {
+        if next_marble_score != 0 && next_marble_score.is_multiple_of(23) {
+            self.place_23_marble(next_marble_score);
+        } else {
+            self.place_normal_marble(next_marble_score);
+        }
+    }
+
+    fn place_normal_marble(&mut self, marble: usize) {
+        self.placed_marbles.rotate(2);
+        self.placed_marbles.push_front(marble);
+    }
+
+    fn place_23_marble(&mut self, marble: usize) {
+        self.placed_marbles.rotate(-7);
+        let taken_marble = self.placed_marbles.pop_front().expect("Could not take marble");
+
+        let player = marble % self.player_scores.len();
+        self.player_scores[player] += marble + taken_marble;
+    }
+
+    /// The player with the highest score and their score, which is 0 when
+    /// the game ends before anyone scores.
+    fn highest_score(&self) -> (usize, usize) {
+        let mut high_player = 0usize;
+        let mut high_score = 0usize;
+        for (player, score) in self.player_scores.iter().enumerate() {
+            if *score > high_score {
+                high_player = player;
+                high_score = *score;
+            }
+        }
+        return (high_player, high_score);
+    }
+}
+
+trait Rotating {
+    fn rotate(&mut self, steps: i32);
+}
+
+impl<T> Rotating for VecDeque<T> {
+    fn rotate(&mut self, steps: i32) {
+        if steps == 0 {
+            return;
+        }
+
+        let clockwise = steps > 0;
+        if clockwise {
+            for _ in 0..steps.abs() {
+                let v = self.pop_front().expect("Can't rotate CW");
+                self.push_back(v);
+            }
+        } else {
+            for _ in 0..steps.abs() {
+                let v = self.pop_back().expect("Can't rotate CCW");
+                self.push_front(v);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_setup_from_string() {
+        let input = "9 players; last marble is worth 25 points";
+        let result = input.parse::<GameSetup>().unwrap();
+        assert_eq!(9, result.num_players);
+        assert_eq!(25 * 100, result.last_marble_score);
+    }
+
+    #[test]
+    fn test_game_state_from_setup() {
+        let setup = GameSetup { num_players: 9, last_marble_score: 25 };
+        let result = GameState::new(setup);
+        assert_eq!(9, result.setup.num_players);
+        assert_eq!(25, result.setup.last_marble_score);
+        assert_eq!(vec![0; 9], result.player_scores);
+        assert_eq!(1, result.placed_marbles.len());
+    }
+
+    #[test]
+    fn test_example_state_from_setup() {
+        let setup = GameSetup { num_players: 9, last_marble_score: 25 };
+        let mut game = GameState::new(setup);
+
+        game.play();
+
+        let (high_player, high_score) = game.highest_score();
+        assert_eq!(5, high_player);
+        assert_eq!(32, high_score);
+
+    }
+
+    #[test]
+    fn test_nobody_scores() {
+        let result = process("9 players; last marble is worth 0 points");
+        assert_eq!(Ok(0), result);
+    }
+
+    #[test]
+    fn test_game_setup_from_bad_string() {
+        let input = "9 players; last marble is worth lots points";
+        let result = input.parse::<GameSetup>().map(|setup| setup.num_players);
+        assert_eq!(Err(ParseError::new(33, "lots", "expected a marble score")), result);
+
+        let input = "0 players; last marble is worth 25 points";
+        let result = input.parse::<GameSetup>().map(|setup| setup.num_players);
+        assert_eq!(Err(ParseError::new(1, "0", "expected at least one player")), result);
+
+        let input = "9 elves; last marble is worth 25 points";
+        assert!(input.parse::<GameSetup>().is_err());
+    }
+}
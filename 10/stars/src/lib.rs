@@ -0,0 +1,304 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+use regex::Regex;
+use std::sync::LazyLock;
+use std::cmp;
+use std::str::FromStr;
+use image::{Luma, GrayImage};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(message(input)?.into())
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        let mut starfield = starfield_from(input)?;
+        Ok(align_starfield(&mut starfield).into())
+    }
+}
+
+pub fn process(input: &str) -> Result<i32, Error> {
+    let mut starfield = starfield_from(input)?;
+    let second = align_starfield(&mut starfield);
+    starfield_to_bmp(&starfield, "message");
+
+    return Ok(second);
+}
+
+/// Renders the message the stars spell out once they've lined up.
+pub fn message(input: &str) -> Result<String, Error> {
+    let mut starfield = starfield_from(input)?;
+    align_starfield(&mut starfield);
+
+    return Ok(starfield_to_string(&starfield));
+}
+
+/// Moves the stars on until they're as tightly packed as they'll get,
+/// returning how many seconds that took.
+fn align_starfield(starfield: &mut Vec<Star>) -> i32 {
+    let mut last_area = i64::MAX;
+    let mut second = 0;
+
+    loop {
+        starfield_step(starfield, StepDirection::Forwards);
+        let (top_left, bottom_right) = starfield_extent(starfield);
+        let area = (bottom_right.x - top_left.x) * (bottom_right.y - top_left.y);
+        if area > last_area {
+            break;
+        }
+        last_area = area;
+        second += 1;
+    }
+    starfield_step(starfield, StepDirection::Backwards);
+
+    return second;
+}
+
+fn starfield_from(string: &str) -> Result<Vec<Star>, Error> {
+    let starfield: Vec<Star> = aoc_common::parse_lines(string)?;
+    if starfield.is_empty() {
+        return Err(Error::Invalid(String::from("expected at least one star")));
+    }
+
+    return Ok(starfield);
+}
+
+fn starfield_extent(starfield: &Vec<Star>) -> (Point, Point) {
+    let mut top_left = Point { x: i64::MAX, y: i64::MAX };
+    let mut bottom_right = Point { x: i64::MIN, y: i64::MIN };
+
+    for star in starfield {
+        top_left.x = cmp::min(top_left.x, star.position.x);
+        top_left.y = cmp::min(top_left.y, star.position.y);
+
+        bottom_right.x = cmp::max(bottom_right.x, star.position.x);
+        bottom_right.y = cmp::max(bottom_right.y, star.position.y);
+    }
+
+    (top_left, bottom_right)
+}
+
+fn starfield_to_string(starfield: &Vec<Star>) -> String {
+    let mut output = String::new();
+    let (top_left, bottom_right) = starfield_extent(starfield);
+
+    for y in top_left.y..=bottom_right.y {
+        for x in top_left.x..=bottom_right.x {
+            let plot = Point { x, y };
+            let has_star = starfield.iter().any( |star| { star.position == plot } );
+            if has_star {
+                output += "*";
+            } else {
+                output += " ";
+            }
+        }
+        output += "\n";
+    }
+
+    return output;
+}
+
+fn starfield_to_bmp(starfield: &Vec<Star>, name: &str) {
+    let (top_left, bottom_right) = starfield_extent(starfield);
+
+    let starfield_width = bottom_right.x - top_left.x;
+    let starfield_height = bottom_right.y - top_left.y;
+
+    let pixel = Luma([255]);
+    let mut img = GrayImage::new((starfield_width + 1) as u32, (starfield_height + 1) as u32);
+    for star in starfield {
+        let star_pos_x = star.position.x + -top_left.x;
+        let star_pos_y = star.position.y + -top_left.y;
+
+        img.put_pixel(star_pos_x as u32, star_pos_y as u32,  pixel);
+    }
+
+    let name = format!("{}.png", name);
+    img.save(name).unwrap();
+}
+
+fn starfield_step(starfield: &mut Vec<Star>, direction: StepDirection) {
+    let sign_change = match direction {
+        StepDirection::Forwards => 1,
+        StepDirection::Backwards => -1,
+    };
+
+    for star in starfield {
+        star.position.x += star.velocity.dx * sign_change;
+        star.position.y += star.velocity.dy * sign_change;
+    }
+}
+
+enum StepDirection {
+    Forwards,
+    Backwards,
+}
+
+#[derive(PartialEq)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+struct Velocity {
+    dx: i64,
+    dy: i64,
+}
+
+struct Star {
+    position: Point,
+    velocity: Velocity,
+}
+
+/// A star, like `position=< 9,  1> velocity=< 0,  2>`.
+static STAR_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^position=<(?P<x>[^,]*),(?P<y>[^>]*)>\s*velocity=<(?P<dx>[^,]*),(?P<dy>[^>]*)>$").expect("Malformed regex"));
+
+impl FromStr for Star {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Star, ParseError> {
+        let captures = match STAR_RE.captures(string) {
+            Some(captures) => captures,
+            None => return Err(ParseError::new(1, string, "expected a star like 'position=< 9,  1> velocity=< 0,  2>'")),
+        };
+
+        let field = |name: &str, expected: &str| {
+            aoc_common::parse_field(string, captures.name(name).unwrap().as_str().trim(), expected)
+        };
+
+        let x = field("x", "an x position")?;
+        let y = field("y", "a y position")?;
+        let position = Point { x, y };
+
+        let dx = field("dx", "an x velocity")?;
+        let dy = field("dy", "a y velocity")?;
+        let velocity = Velocity { dx, dy };
+
+        Ok(Star { position, velocity })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_star_from_string() {
+        let input = "position=< 9,  1> velocity=< 0,  2>";
+        let result = input.parse::<Star>().unwrap();
+        assert_eq!(9, result.position.x);
+        assert_eq!(1, result.position.y);
+        assert_eq!(0, result.velocity.dx);
+        assert_eq!(2, result.velocity.dy);
+    }
+
+    #[test]
+    fn test_starfield_from_string() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  1>\nposition=< 6, 10> velocity=<-2, -1>\nposition=< 2, -4> velocity=< 2,  2>\nposition=<-6, 10> velocity=< 2, -2>\nposition=< 1,  8> velocity=< 1, -1>\nposition=< 1,  7> velocity=< 1,  0>\nposition=<-3, 11> velocity=< 1, -2>\nposition=< 7,  6> velocity=<-1, -1>\nposition=<-2,  3> velocity=< 1,  0>\nposition=<-4,  3> velocity=< 2,  0>\nposition=<10, -3> velocity=<-1,  1>\nposition=< 5, 11> velocity=< 1, -2>\nposition=< 4,  7> velocity=< 0, -1>\nposition=< 8, -2> velocity=< 0,  1>\nposition=<15,  0> velocity=<-2,  0>\nposition=< 1,  6> velocity=< 1,  0>\nposition=< 8,  9> velocity=< 0, -1>\nposition=< 3,  3> velocity=<-1,  1>\nposition=< 0,  5> velocity=< 0, -1>\nposition=<-2,  2> velocity=< 2,  0>\nposition=< 5, -2> velocity=< 1,  2>\nposition=< 1,  4> velocity=< 2,  1>\nposition=<-2,  7> velocity=< 2, -2>\nposition=< 3,  6> velocity=<-1, -1>\nposition=< 5,  0> velocity=< 1,  0>\nposition=<-6,  0> velocity=< 2,  0>\nposition=< 5,  9> velocity=< 1, -2>\nposition=<14,  7> velocity=<-2,  0>\nposition=<-3,  6> velocity=< 2, -1>\n";
+        let result = starfield_from(input).unwrap();
+        assert_eq!(31, result.len());
+    }
+
+    #[test]
+    fn test_starfield_extent() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  1>\nposition=< 6, 10> velocity=<-2, -1>\nposition=< 2, -4> velocity=< 2,  2>\nposition=<-6, 10> velocity=< 2, -2>\nposition=< 1,  8> velocity=< 1, -1>\nposition=< 1,  7> velocity=< 1,  0>\nposition=<-3, 11> velocity=< 1, -2>\nposition=< 7,  6> velocity=<-1, -1>\nposition=<-2,  3> velocity=< 1,  0>\nposition=<-4,  3> velocity=< 2,  0>\nposition=<10, -3> velocity=<-1,  1>\nposition=< 5, 11> velocity=< 1, -2>\nposition=< 4,  7> velocity=< 0, -1>\nposition=< 8, -2> velocity=< 0,  1>\nposition=<15,  0> velocity=<-2,  0>\nposition=< 1,  6> velocity=< 1,  0>\nposition=< 8,  9> velocity=< 0, -1>\nposition=< 3,  3> velocity=<-1,  1>\nposition=< 0,  5> velocity=< 0, -1>\nposition=<-2,  2> velocity=< 2,  0>\nposition=< 5, -2> velocity=< 1,  2>\nposition=< 1,  4> velocity=< 2,  1>\nposition=<-2,  7> velocity=< 2, -2>\nposition=< 3,  6> velocity=<-1, -1>\nposition=< 5,  0> velocity=< 1,  0>\nposition=<-6,  0> velocity=< 2,  0>\nposition=< 5,  9> velocity=< 1, -2>\nposition=<14,  7> velocity=<-2,  0>\nposition=<-3,  6> velocity=< 2, -1>\n";
+        let starfield = starfield_from(input).unwrap();
+        let (top_left, bottom_right) = starfield_extent(&starfield);
+        assert_eq!(-6, top_left.x);
+        assert_eq!(-4, top_left.y);
+        assert_eq!(15, bottom_right.x);
+        assert_eq!(11, bottom_right.y);
+    }
+
+    #[test]
+    fn test_starfield_step() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  1>\nposition=< 6, 10> velocity=<-2, -1>\nposition=< 2, -4> velocity=< 2,  2>\nposition=<-6, 10> velocity=< 2, -2>\nposition=< 1,  8> velocity=< 1, -1>\nposition=< 1,  7> velocity=< 1,  0>\nposition=<-3, 11> velocity=< 1, -2>\nposition=< 7,  6> velocity=<-1, -1>\nposition=<-2,  3> velocity=< 1,  0>\nposition=<-4,  3> velocity=< 2,  0>\nposition=<10, -3> velocity=<-1,  1>\nposition=< 5, 11> velocity=< 1, -2>\nposition=< 4,  7> velocity=< 0, -1>\nposition=< 8, -2> velocity=< 0,  1>\nposition=<15,  0> velocity=<-2,  0>\nposition=< 1,  6> velocity=< 1,  0>\nposition=< 8,  9> velocity=< 0, -1>\nposition=< 3,  3> velocity=<-1,  1>\nposition=< 0,  5> velocity=< 0, -1>\nposition=<-2,  2> velocity=< 2,  0>\nposition=< 5, -2> velocity=< 1,  2>\nposition=< 1,  4> velocity=< 2,  1>\nposition=<-2,  7> velocity=< 2, -2>\nposition=< 3,  6> velocity=<-1, -1>\nposition=< 5,  0> velocity=< 1,  0>\nposition=<-6,  0> velocity=< 2,  0>\nposition=< 5,  9> velocity=< 1, -2>\nposition=<14,  7> velocity=<-2,  0>\nposition=<-3,  6> velocity=< 2, -1>\n";
+        let mut starfield = starfield_from(input).unwrap();
+        starfield_step(&mut starfield, StepDirection::Forwards);
+        let result = starfield_to_string(&starfield);
+
+        let expected = "        *    *    \n      *     *     \n*         *      *\n                  \n    *             \n  **         *    \n    * *           \n   ** **  *       \n      * *         \n      *   *     * \n*           *     \n  *     * *       \n";
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  1>\nposition=< 6, 10> velocity=<-2, -1>\nposition=< 2, -4> velocity=< 2,  2>\nposition=<-6, 10> velocity=< 2, -2>\nposition=< 1,  8> velocity=< 1, -1>\nposition=< 1,  7> velocity=< 1,  0>\nposition=<-3, 11> velocity=< 1, -2>\nposition=< 7,  6> velocity=<-1, -1>\nposition=<-2,  3> velocity=< 1,  0>\nposition=<-4,  3> velocity=< 2,  0>\nposition=<10, -3> velocity=<-1,  1>\nposition=< 5, 11> velocity=< 1, -2>\nposition=< 4,  7> velocity=< 0, -1>\nposition=< 8, -2> velocity=< 0,  1>\nposition=<15,  0> velocity=<-2,  0>\nposition=< 1,  6> velocity=< 1,  0>\nposition=< 8,  9> velocity=< 0, -1>\nposition=< 3,  3> velocity=<-1,  1>\nposition=< 0,  5> velocity=< 0, -1>\nposition=<-2,  2> velocity=< 2,  0>\nposition=< 5, -2> velocity=< 1,  2>\nposition=< 1,  4> velocity=< 2,  1>\nposition=<-2,  7> velocity=< 2, -2>\nposition=< 3,  6> velocity=<-1, -1>\nposition=< 5,  0> velocity=< 1,  0>\nposition=<-6,  0> velocity=< 2,  0>\nposition=< 5,  9> velocity=< 1, -2>\nposition=<14,  7> velocity=<-2,  0>\nposition=<-3,  6> velocity=< 2, -1>\n";
+        let mut starfield = starfield_from(input).unwrap();
+        starfield_step(&mut starfield, StepDirection::Forwards);
+        starfield_step(&mut starfield, StepDirection::Forwards);
+        starfield_step(&mut starfield, StepDirection::Forwards);
+        let result = starfield_to_string(&starfield);
+
+        let expected = "*   *  ***\n*   *   * \n*   *   * \n*****   * \n*   *   * \n*   *   * \n*   *   * \n*   *  ***\n";
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_message() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  1>\nposition=< 6, 10> velocity=<-2, -1>\nposition=< 2, -4> velocity=< 2,  2>\nposition=<-6, 10> velocity=< 2, -2>\nposition=< 1,  8> velocity=< 1, -1>\nposition=< 1,  7> velocity=< 1,  0>\nposition=<-3, 11> velocity=< 1, -2>\nposition=< 7,  6> velocity=<-1, -1>\nposition=<-2,  3> velocity=< 1,  0>\nposition=<-4,  3> velocity=< 2,  0>\nposition=<10, -3> velocity=<-1,  1>\nposition=< 5, 11> velocity=< 1, -2>\nposition=< 4,  7> velocity=< 0, -1>\nposition=< 8, -2> velocity=< 0,  1>\nposition=<15,  0> velocity=<-2,  0>\nposition=< 1,  6> velocity=< 1,  0>\nposition=< 8,  9> velocity=< 0, -1>\nposition=< 3,  3> velocity=<-1,  1>\nposition=< 0,  5> velocity=< 0, -1>\nposition=<-2,  2> velocity=< 2,  0>\nposition=< 5, -2> velocity=< 1,  2>\nposition=< 1,  4> velocity=< 2,  1>\nposition=<-2,  7> velocity=< 2, -2>\nposition=< 3,  6> velocity=<-1, -1>\nposition=< 5,  0> velocity=< 1,  0>\nposition=<-6,  0> velocity=< 2,  0>\nposition=< 5,  9> velocity=< 1, -2>\nposition=<14,  7> velocity=<-2,  0>\nposition=<-3,  6> velocity=< 2, -1>\n";
+        let result = message(input).unwrap();
+
+        let expected = "*   *  ***\n*   *   * \n*   *   * \n*****   * \n*   *   * \n*   *   * \n*   *   * \n*   *  ***\n";
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_align_starfield() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7,  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  1>\nposition=< 6, 10> velocity=<-2, -1>\nposition=< 2, -4> velocity=< 2,  2>\nposition=<-6, 10> velocity=< 2, -2>\nposition=< 1,  8> velocity=< 1, -1>\nposition=< 1,  7> velocity=< 1,  0>\nposition=<-3, 11> velocity=< 1, -2>\nposition=< 7,  6> velocity=<-1, -1>\nposition=<-2,  3> velocity=< 1,  0>\nposition=<-4,  3> velocity=< 2,  0>\nposition=<10, -3> velocity=<-1,  1>\nposition=< 5, 11> velocity=< 1, -2>\nposition=< 4,  7> velocity=< 0, -1>\nposition=< 8, -2> velocity=< 0,  1>\nposition=<15,  0> velocity=<-2,  0>\nposition=< 1,  6> velocity=< 1,  0>\nposition=< 8,  9> velocity=< 0, -1>\nposition=< 3,  3> velocity=<-1,  1>\nposition=< 0,  5> velocity=< 0, -1>\nposition=<-2,  2> velocity=< 2,  0>\nposition=< 5, -2> velocity=< 1,  2>\nposition=< 1,  4> velocity=< 2,  1>\nposition=<-2,  7> velocity=< 2, -2>\nposition=< 3,  6> velocity=<-1, -1>\nposition=< 5,  0> velocity=< 1,  0>\nposition=<-6,  0> velocity=< 2,  0>\nposition=< 5,  9> velocity=< 1, -2>\nposition=<14,  7> velocity=<-2,  0>\nposition=<-3,  6> velocity=< 2, -1>\n";
+        let mut starfield = starfield_from(input).unwrap();
+        let result = align_starfield(&mut starfield);
+        assert_eq!(3, result);
+    }
+
+    #[test]
+    fn test_starfield_from_bad_string() {
+        let input = "position=< 9,  1> velocity=< 0,  2>\nposition=< 7;  0> velocity=<-1,  0>\nposition=< 3, -2> velocity=<-1,  one>\n";
+        let result = starfield_from(input).map(|starfield| starfield.len());
+
+        let expected = Error::Parse(vec![
+            ParseError::new(1, "position=< 7;  0> velocity=<-1,  0>", "expected a star like 'position=< 9,  1> velocity=< 0,  2>'").at_line(2),
+            ParseError::new(34, "one", "expected a y velocity").at_line(3),
+        ]);
+        assert_eq!(Err(expected), result);
+    }
+}
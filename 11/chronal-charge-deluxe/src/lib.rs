@@ -0,0 +1,186 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, Solution};
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        let serial_number = read_serial_number(input)?;
+        let (x, y, _, side) = process(serial_number);
+        Ok(format!("{},{},{}", x, y, side).into())
+    }
+
+    fn accepts_inline_input(&self) -> bool {
+        true
+    }
+}
+
+/// Reads the grid serial number, which must be the only thing in the input.
+pub fn read_serial_number(input: &str) -> Result<i64, Error> {
+    let mut serial_numbers: Vec<i64> = aoc_common::parse_lines_with(input, |line| {
+        aoc_common::parse_field(line, line, "a grid serial number")
+    })?;
+    if serial_numbers.len() != 1 {
+        return Err(Error::Invalid(format!("expected one grid serial number, found {}", serial_numbers.len())));
+    }
+
+    return Ok(serial_numbers.remove(0));
+}
+
+pub fn process(serial_number: i64) -> (i64, i64, i64, i64) {
+    let grid = charge_grid(serial_number);
+    max_power(&grid)
+}
+
+/// The most powerful square with sides `side` cells long, as the x and y of
+/// its top left cell and its power.
+pub fn process_square(serial_number: i64, side: i64) -> (i64, i64, i64) {
+    let grid = charge_grid(serial_number);
+    max_power_for_size(&grid, side)
+}
+
+fn charge_grid(serial_number: i64) -> Vec<i64> {
+    let side = 300usize;
+    let mut grid = vec![0i64; side * side];
+    for y in 0..side {
+        for x in 0..side {
+            grid[y * side + x] = power_level(x as i64, y as i64, serial_number);
+        }
+    }
+
+    return grid;
+}
+
+fn sample_grid(grid: &[i64], start_x: i64, start_y: i64, side: i64) -> i64 {
+    let mut total = 0i64;
+    let start_x= start_x as usize;
+    let start_y= start_y as usize;
+    for y in start_y..(start_y + side as usize) {
+        for x in start_x..(start_x + side as usize) {
+            total += grid[y * 300 + x];
+        }
+    }
+    return total;
+}
+
+fn power_level(x: i64, y: i64, serial: i64) -> i64 {
+    // Serial numbers near the limits of an i64 would overflow it
+    let rack_id = (x + 10) as i128;
+    let intermediate_power = (rack_id * y as i128 + serial as i128) * rack_id;
+    // Powers below 100 have a hundreds digit of 0, and negative powers
+    // take the digit of their size
+    let base_power = ((intermediate_power / 100).abs() % 10) as i64;
+    return base_power - 5;
+}
+
+fn max_power_for_size(grid: &[i64], side: i64) -> (i64, i64, i64) {
+    let mut power = i64::MIN;
+    let mut power_x = 0i64;
+    let mut power_y = 0i64;
+    for y in 0..(300 - side) {
+        for x in 0..(300 - side) {
+            let sample_power = sample_grid(grid, x, y, side);
+            if sample_power > power {
+                power = sample_power;
+                power_x = x;
+                power_y = y;
+            }
+        }
+    }
+
+    (power_x, power_y, power)
+}
+
+fn max_power(grid: &[i64]) -> (i64, i64, i64, i64) {
+    let mut max_power = 0i64;
+    let mut max_side = 0i64;
+    let mut max_x = 0i64;
+    let mut max_y = 0i64;
+
+    for side in 1..=300 {
+        let (x, y, power) = max_power_for_size(grid, side);
+        if power > max_power {
+            max_power = power;
+            max_side = side;
+            max_x = x;
+            max_y = y;
+        }
+    }
+
+    return (max_x, max_y, max_power, max_side );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_level() {
+        let result = power_level(3, 5, 8);
+        assert_eq!(4, result);
+    }
+
+    #[test]
+    fn test_power_level_without_hundreds() {
+        assert_eq!(-5, power_level(0, 0, 1));
+    }
+
+    #[test]
+    fn test_power_level_with_negative_serial() {
+        assert_eq!(-5, power_level(0, 0, -5));
+        assert_eq!(0, power_level(0, 0, -350));
+        assert_eq!(-4, power_level(299, 299, i64::MAX));
+        assert_eq!(3, power_level(299, 299, i64::MIN));
+    }
+
+    #[test]
+    fn test_example_exact_1() {
+        let grid = charge_grid(18);
+        let result = sample_grid(&grid, 33, 45, 3);
+        assert_eq!(29, result);
+    }
+
+    #[test]
+    fn test_example_exact_2() {
+        let grid = charge_grid(42);
+        let result = sample_grid(&grid, 21, 61, 3);
+        assert_eq!(30, result);
+    }
+
+    #[test]
+    fn test_example_1() {
+        let grid = charge_grid(18);
+        let (x, y, power, side) = max_power(&grid);
+        assert_eq!(90, x);
+        assert_eq!(269, y);
+        assert_eq!(113, power);
+        assert_eq!(16, side);
+    }
+
+    #[test]
+    fn test_example_2() {
+        let grid = charge_grid(42);
+        let (x, y, power, side) = max_power(&grid);
+        assert_eq!(232, x);
+        assert_eq!(251, y);
+        assert_eq!(119, power);
+        assert_eq!(12, side);
+    }
+}
@@ -0,0 +1,169 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, Solution};
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        let serial_number = read_serial_number(input)?;
+        let (x, y, _) = process(serial_number);
+        Ok(format!("{},{}", x, y).into())
+    }
+
+    fn accepts_inline_input(&self) -> bool {
+        true
+    }
+}
+
+/// Reads the grid serial number, which must be the only thing in the input.
+pub fn read_serial_number(input: &str) -> Result<i64, Error> {
+    let mut serial_numbers: Vec<i64> = aoc_common::parse_lines_with(input, |line| {
+        aoc_common::parse_field(line, line, "a grid serial number")
+    })?;
+    if serial_numbers.len() != 1 {
+        return Err(Error::Invalid(format!("expected one grid serial number, found {}", serial_numbers.len())));
+    }
+
+    return Ok(serial_numbers.remove(0));
+}
+
+pub fn process(serial_number: i64) -> (i64, i64, i64) {
+    let grid = charge_grid(serial_number);
+    max_power(&grid)
+}
+
+fn charge_grid(serial_number: i64) -> Vec<i64> {
+    let side = 300usize;
+    let mut grid = vec![0i64; side * side];
+    for y in 0..side {
+        for x in 0..side {
+            grid[y * side + x] = power_level(x as i64, y as i64, serial_number);
+        }
+    }
+
+    return grid;
+}
+
+fn sample_grid(grid: &[i64], x: i64, y: i64) -> i64 {
+    let y1 = ((y * 300) + x) as usize;
+    let y2 = (((y + 1) * 300) + x) as usize;
+    let y3 = (((y + 2) * 300) + x) as usize;
+
+    grid[y1] + grid[y1 + 1] + grid[y1 + 2] +
+    grid[y2] + grid[y2 + 1] + grid[y2 + 2] +
+    grid[y3] + grid[y3 + 1] + grid[y3 + 2]
+}
+
+fn power_level(x: i64, y: i64, serial: i64) -> i64 {
+    // Serial numbers near the limits of an i64 would overflow it
+    let rack_id = (x + 10) as i128;
+    let intermediate_power = (rack_id * y as i128 + serial as i128) * rack_id;
+    // Powers below 100 have a hundreds digit of 0, and negative powers
+    // take the digit of their size
+    let base_power = ((intermediate_power / 100).abs() % 10) as i64;
+    return base_power - 5;
+}
+
+fn max_power(grid: &[i64]) -> (i64, i64, i64) {
+    let mut power = i64::MIN;
+    let mut power_x = 0i64;
+    let mut power_y = 0i64;
+    for y in 0..(300 - 3i64) {
+        for x in 0..(300 - 3i64) {
+            let sample_power = sample_grid(grid, x, y);
+            if sample_power > power {
+                power = sample_power;
+                power_x = x;
+                power_y = y;
+            }
+        }
+    }
+
+    (power_x, power_y, power)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_level() {
+        let result = power_level(3, 5, 8);
+        assert_eq!(4, result);
+    }
+
+    #[test]
+    fn test_power_level_without_hundreds() {
+        assert_eq!(-5, power_level(0, 0, 1));
+    }
+
+    #[test]
+    fn test_power_level_with_negative_serial() {
+        assert_eq!(-5, power_level(0, 0, -5));
+        assert_eq!(0, power_level(0, 0, -350));
+        assert_eq!(-4, power_level(299, 299, i64::MAX));
+        assert_eq!(3, power_level(299, 299, i64::MIN));
+    }
+
+    #[test]
+    fn test_solve_negative_serial() {
+        assert!(Part1.solve("-5").is_ok());
+    }
+
+    #[test]
+    fn test_example_exact_1() {
+        let grid = charge_grid(18);
+        let result = sample_grid(&grid, 33, 45);
+        assert_eq!(29, result);
+    }
+
+    #[test]
+    fn test_example_exact_2() {
+        let grid = charge_grid(42);
+        let result = sample_grid(&grid, 21, 61);
+        assert_eq!(30, result);
+    }
+
+    #[test]
+    fn test_example_1() {
+        let grid = charge_grid(18);
+        let (x, y, power) = max_power(&grid);
+        assert_eq!(33, x);
+        assert_eq!(45, y);
+        assert_eq!(29, power);
+    }
+
+    #[test]
+    fn test_example_2() {
+        let grid = charge_grid(42);
+        let (x, y, power) = max_power(&grid);
+        assert_eq!(21, x);
+        assert_eq!(61, y);
+        assert_eq!(30, power);
+    }
+
+    #[test]
+    fn test_read_serial_number() {
+        assert_eq!(Ok(18), read_serial_number("18\n"));
+        assert!(read_serial_number("eighteen\n").is_err());
+        assert!(read_serial_number("18\n42\n").is_err());
+        assert!(read_serial_number("").is_err());
+    }
+}
@@ -0,0 +1,332 @@
+#![allow(clippy::needless_return)]
+
+use aoc_common::{Answer, Error, ParseError, Solution};
+use std::collections::VecDeque;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn input_path(&self) -> &'static str {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Error> {
+        Ok(process(input)?.into())
+    }
+}
+
+pub fn process(input: &str) -> Result<i64, Error> {
+    sum_after(input, 20)
+}
+
+/// The sum of the numbers of the pots with plants in them after
+/// `generations` generations.
+pub fn sum_after(input: &str, generations: usize) -> Result<i64, Error> {
+    let mut game = read_game_from_text(input)?;
+
+    for _ in 1..=generations {
+        step(&mut game);
+    }
+
+    Ok(game_sum(&game))
+}
+
+/// The initial state, like `initial state: #..#.#`.
+static INITIAL_STATE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^initial state: (?P<state>\S+)$").expect("Broken regex"));
+
+fn read_initial_input_line(line: &str) -> Result<VecDeque<usize>, ParseError> {
+    let captures = match INITIAL_STATE_RE.captures(line) {
+        Some(captures) => captures,
+        None => return Err(ParseError::new(1, line, "expected an initial state like 'initial state: #..#.#'")),
+    };
+    let state_str = captures.name("state").unwrap().as_str();
+
+    let mut state = VecDeque::new();
+    for (index, c) in state_str.char_indices() {
+        state.push_back(read_pot(line, &state_str[index..index + c.len_utf8()])?);
+    }
+    return Ok(state);
+}
+
+/// Reads a pot, which is '#' if it has a plant in it and '.' if not.
+fn read_pot(line: &str, pot: &str) -> Result<usize, ParseError> {
+    match pot {
+        "#" => Ok(1),
+        "." => Ok(0),
+        _ => Err(ParseError::within(line, pot, "expected '#' or '.'")),
+    }
+}
+
+struct Rule {
+    pattern: usize,
+    result: usize,
+}
+
+/// A rule, like `...## => #`.
+static RULE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?P<pattern>[^ ]{5}) => (?P<result>[^ ])$").expect("Broken regex"));
+
+fn read_rule_from_line(line: &str) -> Result<Rule, ParseError> {
+    let captures = match RULE_RE.captures(line) {
+        Some(captures) => captures,
+        None => return Err(ParseError::new(1, line, "expected a rule like '...## => #'")),
+    };
+    let pattern_str = captures.name("pattern").unwrap().as_str();
+    let result_str = captures.name("result").unwrap().as_str();
+
+    let mut pattern = 0;
+    for (index, c) in pattern_str.char_indices() {
+        let pot = read_pot(line, &pattern_str[index..index + c.len_utf8()])?;
+        pattern |= pot << (pattern_str.len() - 1 - index);
+    }
+
+    let result = read_pot(line, result_str)?;
+
+    Ok(Rule { pattern, result })
+}
+
+struct Game {
+    state: VecDeque<usize>,
+    zero_index: usize,
+    rules: Vec<Rule>,
+}
+
+fn read_game_from_text(text: &str) -> Result<Game, Error> {
+    // The first line is the initial state, every line after is a rule
+    let mut first_line = true;
+    let mut state = None;
+    let rules = aoc_common::parse_lines_with(text, |line| {
+        if first_line {
+            first_line = false;
+            state = Some(read_initial_input_line(line)?);
+            return Ok(None);
+        }
+        Ok(Some(read_rule_from_line(line)?))
+    })?;
+
+    let state = match state {
+        Some(state) => state,
+        None => return Err(Error::Invalid(String::from("expected an initial state"))),
+    };
+    let rules = rules.into_iter().flatten().collect();
+
+    return Ok(Game { state, zero_index: 0, rules })
+}
+
+fn grow_state(state: &mut VecDeque<usize>) -> usize {
+    if state[state.len() - 1] == 1 {
+        state.push_back(0);
+        state.push_back(0);
+        state.push_back(0);
+        state.push_back(0);
+    } else if state[state.len() - 2] == 1 {
+        state.push_back(0);
+        state.push_back(0);
+        state.push_back(0);
+    } else if state[state.len() - 3] == 1 {
+        state.push_back(0);
+        state.push_back(0);
+    } else if state[state.len() - 4] == 1 {
+        state.push_back(0);
+    }
+
+    if state[0] == 1 {
+        state.push_front(0);
+        state.push_front(0);
+        state.push_front(0);
+        state.push_front(0);
+        return 4;
+    } else if state[1] == 1 {
+        state.push_front(0);
+        state.push_front(0);
+        state.push_front(0);
+        return 3;
+    } else if state[2] == 1 {
+        state.push_front(0);
+        state.push_front(0);
+        return 2;
+    } else if state[3] == 1 {
+        state.push_front(0);
+        return 1;
+    } else {
+        return 0;
+    }
+
+}
+
+fn step(game: &mut Game ) {
+    game.zero_index += grow_state(&mut game.state);
+
+    let in_state = &game.state;
+    let mut out_state = in_state.clone();
+
+    for i in 0..in_state.len() - 5 {
+        let window = in_state[i] << 4 | in_state[i + 1] << 3 | in_state[i + 2] << 2 | in_state[i + 3] << 1 | in_state[i + 4];
+        if let Some(rule) = game.rules.iter().find(|r| {r.pattern == window} ) {
+            out_state[i + 2] = rule.result;
+        } else {
+            out_state[i + 2] = 0;
+        }
+    }
+
+    game.state = out_state;
+}
+
+fn game_sum(game: &Game) -> i64 {
+    let mut sum = 0;
+
+    for (index, value) in game.state.iter().enumerate() {
+        if *value == 1 {
+            let index = index as i64;
+            let zero = game.zero_index as i64;
+            sum += index - zero;
+        }
+    }
+
+    return sum;
+}
+
+#[cfg(test)]
+fn game_state_to_string(game: &Game) -> String {
+    let mut s = String::new();
+    for (index, value) in game.state.iter().enumerate() {
+        if index == game.zero_index {
+            if *value == 1 {
+                s += "O";
+            } else {
+                s += "|";
+            }
+        } else if *value == 1 {
+            s += "#";
+        } else {
+            s += ".";
+        }
+    }
+    return s;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_setup_from_string() {
+        let input = "initial state: #..#.#..##......###...###";
+        let result = read_initial_input_line(input).unwrap();
+
+        assert_eq!(25, result.len());
+        assert_eq!( 1, result[ 0]);
+        assert_eq!( 0, result[ 1]);
+        assert_eq!( 0, result[ 2]);
+        assert_eq!( 1, result[ 3]);
+        assert_eq!( 0, result[ 4]);
+        assert_eq!( 1, result[ 5]);
+        assert_eq!( 0, result[ 6]);
+        assert_eq!( 0, result[ 7]);
+        assert_eq!( 1, result[ 8]);
+        assert_eq!( 1, result[ 9]);
+        assert_eq!( 0, result[10]);
+        assert_eq!( 0, result[11]);
+        assert_eq!( 0, result[12]);
+        assert_eq!( 0, result[13]);
+        assert_eq!( 0, result[14]);
+        assert_eq!( 0, result[15]);
+        assert_eq!( 1, result[16]);
+        assert_eq!( 1, result[17]);
+        assert_eq!( 1, result[18]);
+        assert_eq!( 0, result[19]);
+        assert_eq!( 0, result[20]);
+        assert_eq!( 0, result[21]);
+        assert_eq!( 1, result[22]);
+        assert_eq!( 1, result[23]);
+        assert_eq!( 1, result[24]);
+    }
+
+    #[test]
+    fn test_rule_from_string() {
+        let input = "...## => #";
+        let result = read_rule_from_line(input).unwrap();
+
+        assert_eq!(1, result.result);
+        assert_eq!(3, result.pattern);
+    }
+
+    #[test]
+    fn test_game_from_string() {
+        let input = "initial state: #..#.#..##......###...###\n\n...## => #\n..#.. => #\n.#... => #\n.#.#. => #\n.#.## => #\n.##.. => #\n.#### => #\n#.#.# => #\n#.### => #\n##.#. => #\n##.## => #\n###.. => #\n###.# => #\n####. => #\n";
+        let result = read_game_from_text(input).unwrap();
+
+        assert_eq!(25, result.state.len());
+        assert_eq!( 1, result.state[ 0]);
+        assert_eq!( 0, result.state[ 1]);
+        assert_eq!( 0, result.state[ 2]);
+        assert_eq!( 1, result.state[ 3]);
+        assert_eq!( 0, result.state[ 4]);
+        assert_eq!( 1, result.state[ 5]);
+        assert_eq!( 0, result.state[ 6]);
+        assert_eq!( 0, result.state[ 7]);
+        assert_eq!( 1, result.state[ 8]);
+        assert_eq!( 1, result.state[ 9]);
+        assert_eq!( 0, result.state[10]);
+        assert_eq!( 0, result.state[11]);
+        assert_eq!( 0, result.state[12]);
+        assert_eq!( 0, result.state[13]);
+        assert_eq!( 0, result.state[14]);
+        assert_eq!( 0, result.state[15]);
+        assert_eq!( 1, result.state[16]);
+        assert_eq!( 1, result.state[17]);
+        assert_eq!( 1, result.state[18]);
+        assert_eq!( 0, result.state[19]);
+        assert_eq!( 0, result.state[20]);
+        assert_eq!( 0, result.state[21]);
+        assert_eq!( 1, result.state[22]);
+        assert_eq!( 1, result.state[23]);
+        assert_eq!( 1, result.state[24]);
+
+        assert_eq!(14, result.rules.len());
+        assert_eq!(0, result.zero_index);
+    }
+
+    #[test]
+    fn test_example() {
+        let input = "initial state: #..#.#..##......###...###\n\n...## => #\n..#.. => #\n.#... => #\n.#.#. => #\n.#.## => #\n.##.. => #\n.#### => #\n#.#.# => #\n#.### => #\n##.#. => #\n##.## => #\n###.. => #\n###.# => #\n####. => #\n";
+        let mut result = read_game_from_text(input).unwrap();
+
+        println!(" 0: {}", game_state_to_string(&result));
+        for i in 1..=20 {
+            step(&mut result);
+            println!("{:>2}: {}", i, game_state_to_string(&result));
+        }
+
+        assert_eq!("....#.|..##....#####...#######....#.#..##...", game_state_to_string(&result));
+        assert_eq!(325, game_sum(&result));
+    }
+
+    #[test]
+    fn test_game_from_bad_string() {
+        let input = "initial state: #..#.#..##..o...###...###\n\n...## => #\n..#. => #\n.#... => X\n";
+        let result = read_game_from_text(input).map(|game| game.rules.len());
+
+        let expected = Error::Parse(vec![
+            ParseError::new(28, "o", "expected '#' or '.'").at_line(1),
+            ParseError::new(1, "..#. => #", "expected a rule like '...## => #'").at_line(4),
+            ParseError::new(10, "X", "expected '#' or '.'").at_line(5),
+        ]);
+        assert_eq!(Err(expected), result);
+
+        assert!(read_game_from_text("\n").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::error;
+use std::fmt;
+
+/// A problem with one line of a puzzle input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// The line of the input the problem is on, counting from 1. Zero until
+    /// the error has been placed with `at_line`.
+    pub line: usize,
+    /// The column of the line the offending text starts at, counting from 1.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    /// What was expected instead.
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, text: &str, reason: &str) -> ParseError {
+        ParseError {
+            line: 0,
+            column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Creates an error for `text`, a slice of `line`, working out the column
+    /// from where the slice sits in the line. Text from anywhere else is put
+    /// at the start of the line.
+    pub fn within(line: &str, text: &str, reason: &str) -> ParseError {
+        let line_start = line.as_ptr() as usize;
+        let text_start = text.as_ptr() as usize;
+        let inside = text_start >= line_start
+            && text_start + text.len() <= line_start + line.len();
+        let column = if inside { text_start - line_start + 1 } else { 1 };
+
+        ParseError::new(column, text, reason)
+    }
+
+    /// Places the error on a line of the input.
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    /// Moves the error along the line, for when it was found by parsing
+    /// only part of the line.
+    pub fn offset_by(mut self, columns: usize) -> ParseError {
+        self.column += columns;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}, ", self.line)?;
+        }
+        write!(f, "column {}: {}, found '{}'", self.column, self.reason, self.text)
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Why a solution couldn't produce an answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Every line of the puzzle input that couldn't be understood.
+    Parse(Vec<ParseError>),
+    /// The puzzle input could be read but doesn't make sense.
+    Invalid(String),
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Error {
+        Error::Parse(vec![error])
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(errors) => {
+                write!(f, "Malformed input:")?;
+                for error in errors {
+                    write!(f, "\n    {}", error)?;
+                }
+                Ok(())
+            },
+            Error::Invalid(message) => write!(f, "Invalid input: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within_finds_column() {
+        let line = "#1 @ 1,3: 4xfour";
+        let error = ParseError::within(line, &line[12..], "expected a height");
+        assert_eq!(13, error.column);
+        assert_eq!("four", error.text);
+    }
+
+    #[test]
+    fn test_within_unrelated_text() {
+        let error = ParseError::within("#1 @ 1,3: 4x4", "elsewhere", "expected a claim");
+        assert_eq!(1, error.column);
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ParseError::new(3, "x", "expected a number").at_line(7);
+        assert_eq!("line 7, column 3: expected a number, found 'x'", error.to_string());
+
+        let error = Error::Parse(vec![
+            ParseError::new(1, "a", "expected a number").at_line(1),
+            ParseError::new(2, "b", "expected a number").at_line(4),
+        ]);
+        assert_eq!("Malformed input:\n    line 1, column 1: expected a number, found 'a'\n    line 4, column 2: expected a number, found 'b'", error.to_string());
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a puzzle input comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    /// Standard input, asked for with `-`.
+    Stdin,
+    /// A file holding the input.
+    File(PathBuf),
+    /// The input itself, given in place of a path.
+    Inline(String),
+}
+
+impl InputSource {
+    /// Works out what an input argument refers to. `-` is standard input
+    /// and a directory means the `input.txt` inside it. Anything else is a
+    /// file, unless `inline` allows short inputs such as day 11's serial
+    /// number to be given directly and there's no file by that name.
+    pub fn from_arg(arg: &str, inline: bool) -> InputSource {
+        if arg == "-" {
+            return InputSource::Stdin;
+        }
+
+        let path = Path::new(arg);
+        if path.is_dir() {
+            return InputSource::File(path.join("input.txt"));
+        }
+        if inline && !path.exists() {
+            return InputSource::Inline(arg.to_string());
+        }
+
+        InputSource::File(path.to_path_buf())
+    }
+
+    /// Reads the whole input.
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            },
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Inline(input) => Ok(input.clone()),
+        }
+    }
+
+    /// Opens the input to be read a line at a time, for inputs too long to
+    /// hold whole or that never end.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => Ok(Box::new(BufReader::new(fs::File::open(path)?))),
+            InputSource::Inline(input) => Ok(Box::new(io::Cursor::new(input.clone().into_bytes()))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "standard input"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Inline(_) => write!(f, "inline input"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg_stdin() {
+        assert_eq!(InputSource::Stdin, InputSource::from_arg("-", false));
+        assert_eq!(InputSource::Stdin, InputSource::from_arg("-", true));
+    }
+
+    #[test]
+    fn test_from_arg_directory() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let expected = InputSource::File(Path::new(dir).join("input.txt"));
+        assert_eq!(expected, InputSource::from_arg(dir, false));
+    }
+
+    #[test]
+    fn test_from_arg_file() {
+        let file = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        assert_eq!(InputSource::File(PathBuf::from(file)), InputSource::from_arg(file, false));
+        assert_eq!(InputSource::File(PathBuf::from(file)), InputSource::from_arg(file, true));
+    }
+
+    #[test]
+    fn test_from_arg_inline() {
+        let arg = "9 players; last marble is worth 25 points";
+        assert_eq!(InputSource::Inline(arg.to_string()), InputSource::from_arg(arg, true));
+        assert_eq!(InputSource::File(PathBuf::from(arg)), InputSource::from_arg(arg, false));
+        assert_eq!("18", InputSource::from_arg("18", true).read().unwrap());
+    }
+
+    #[test]
+    fn test_open_reads_lines() {
+        let source = InputSource::Inline(String::from("+1\n-2\n"));
+        let lines: Vec<String> = source.open().unwrap().lines().map(|line| line.unwrap()).collect();
+        assert_eq!(vec!["+1", "-2"], lines);
+
+        assert!(InputSource::File(PathBuf::from("no-such-input.txt")).open().is_err());
+    }
+}
@@ -0,0 +1,278 @@
+mod error;
+mod input;
+
+pub use error::{Error, ParseError};
+pub use input::InputSource;
+
+use std::env;
+use std::fmt;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::process;
+use std::str::FromStr;
+
+/// The answer to one part of a day's puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(number: $t) -> Answer {
+                    Answer::Number(number as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(i32, i64, u32, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(text: &'a str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// One part of a day's puzzle, solvable from the text of a puzzle input.
+pub trait Solution {
+    /// The day of December the puzzle was released.
+    fn day(&self) -> u32;
+
+    /// Which part of the day's puzzle this solves, 1 or 2.
+    fn part(&self) -> u32;
+
+    /// The name of the crate providing the solution, used to tell apart
+    /// several solutions to the same part.
+    fn name(&self) -> &'static str;
+
+    /// Where the puzzle input checked in alongside the solution lives.
+    fn input_path(&self) -> &'static str;
+
+    /// Solves the puzzle for the text of a puzzle input.
+    fn solve(&self, input: &str) -> Result<Answer, Error>;
+
+    /// Whether the puzzle input is short enough to be given inline, in
+    /// place of a path.
+    fn accepts_inline_input(&self) -> bool {
+        false
+    }
+}
+
+/// Reads the puzzle input named by the first command line argument, which
+/// can be a file, a directory holding an `input.txt`, or `-` for standard
+/// input. Without an argument `input.txt` in the current directory is read.
+pub fn read_input() -> String {
+    read_input_from_args(false)
+}
+
+/// Reads the puzzle input as `read_input` does, but also accepts the input
+/// itself as the argument.
+pub fn read_input_or_value() -> String {
+    read_input_from_args(true)
+}
+
+/// Reads the puzzle input named by `arg` as `read_input` does, for days
+/// whose command line takes options as well as the input.
+pub fn read_input_from(arg: Option<&str>) -> String {
+    read_input_from_arg(arg, false)
+}
+
+/// Opens the puzzle input named by `arg` to be read a line at a time,
+/// finding it as `read_input_from` does.
+pub fn open_input_from(arg: Option<&str>) -> Box<dyn BufRead> {
+    let source = input_source(arg, false);
+
+    match source.open() {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Failed to open {}: {}", source, error);
+            process::exit(1);
+        },
+    }
+}
+
+fn read_input_from_args(inline: bool) -> String {
+    read_input_from_arg(env::args().nth(1).as_deref(), inline)
+}
+
+fn input_source(arg: Option<&str>, inline: bool) -> InputSource {
+    match arg {
+        Some(arg) => InputSource::from_arg(arg, inline),
+        None => InputSource::from_arg("input.txt", false),
+    }
+}
+
+fn read_input_from_arg(arg: Option<&str>, inline: bool) -> String {
+    let source = input_source(arg, inline);
+
+    match source.read() {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Failed to read {}: {}", source, error);
+            process::exit(1);
+        },
+    }
+}
+
+/// Iterates over the lines of `text` with surrounding whitespace trimmed,
+/// skipping any that end up blank.
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+}
+
+/// Parses every non-blank line of `text` with `parse`. Parsing carries on
+/// past bad lines so that the error lists every problem with the input,
+/// not just the first.
+pub fn parse_lines_with<T, F>(text: &str, mut parse: F) -> Result<Vec<T>, Error>
+    where F: FnMut(&str) -> Result<T, ParseError>
+{
+    parse_numbered_lines_with(text, |_, line| parse(line))
+}
+
+/// Parses every non-blank line of `text` as `parse_lines_with` does, also
+/// handing `parse` the number of the line, counting from 1, for values that
+/// need to say where they came from.
+pub fn parse_numbered_lines_with<T, F>(text: &str, mut parse: F) -> Result<Vec<T>, Error>
+    where F: FnMut(usize, &str) -> Result<T, ParseError>
+{
+    let mut parsed = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match parse(index + 1, trimmed) {
+            Ok(value) => parsed.push(value),
+            Err(error) => {
+                let indent = line.len() - line.trim_start().len();
+                errors.push(error.at_line(index + 1).offset_by(indent));
+            },
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::Parse(errors));
+    }
+
+    Ok(parsed)
+}
+
+/// Parses every non-blank line of `text` with `FromStr`, reporting every
+/// bad line as `parse_lines_with` does.
+pub fn parse_lines<T>(text: &str) -> Result<Vec<T>, Error>
+    where T: FromStr<Err = ParseError>
+{
+    parse_lines_with(text, |line| line.parse())
+}
+
+/// Parses `field`, which must be a slice of `line`, into a number or
+/// similar, describing it as `expected` if it can't be parsed.
+pub fn parse_field<T: FromStr>(line: &str, field: &str, expected: &str) -> Result<T, ParseError> {
+    field.parse()
+        .map_err(|_| ParseError::within(line, field, &format!("expected {}", expected)))
+}
+
+/// Unwraps the result of a solution, or reports the error and exits.
+pub fn unwrap_or_exit<T>(result: Result<T, Error>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        },
+    }
+}
+
+/// Prints the result of a solution.
+pub fn print_result<T: Display>(result: T) {
+    println!("Result: {}\n", result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("-42", Answer::from(-42).to_string());
+        assert_eq!("CABDFE", Answer::from("CABDFE").to_string());
+    }
+
+    #[test]
+    fn test_lines_skips_blank_lines() {
+        let input = "+1\n\n-1\n  \n\n";
+        let result: Vec<&str> = lines(input).collect();
+        assert_eq!(vec!["+1", "-1"], result);
+    }
+
+    #[test]
+    fn test_lines_trims_whitespace() {
+        let input = "  #1 @ 1,3: 4x4 \n\t#2 @ 3,1: 4x4";
+        let result: Vec<&str> = lines(input).collect();
+        assert_eq!(vec!["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4"], result);
+    }
+
+    #[test]
+    fn test_parse_lines_reports_every_bad_line() {
+        let input = "+1\nfive\n\n  -2\n  +x\n";
+        let result = parse_lines_with(input, |line| parse_field::<i32>(line, line, "a number"));
+
+        let expected = Error::Parse(vec![
+            ParseError::new(1, "five", "expected a number").at_line(2),
+            ParseError::new(3, "+x", "expected a number").at_line(5),
+        ]);
+        assert_eq!(Err(expected), result);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "+1\n\n  -2\n";
+        let result = parse_lines_with(input, |line| parse_field::<i32>(line, line, "a number"));
+        assert_eq!(Ok(vec![1, -2]), result);
+    }
+
+    #[test]
+    fn test_parse_numbered_lines() {
+        let input = "+1\n\n  -2\n";
+        let result = parse_numbered_lines_with(input, |number, line| Ok((number, parse_field::<i32>(line, line, "a number")?)));
+        assert_eq!(Ok(vec![(1, 1), (3, -2)]), result);
+    }
+
+    #[test]
+    fn test_parse_field_column() {
+        let line = "9 players; last marble is worth lots points";
+        let result = parse_field::<u32>(line, &line[32..36], "a score");
+        assert_eq!(Err(ParseError::new(33, "lots", "expected a score")), result);
+    }
+
+    #[test]
+    fn test_lines_without_trailing_newline() {
+        let input = "abcde\nfghij";
+        let result: Vec<&str> = lines(input).collect();
+        assert_eq!(vec!["abcde", "fghij"], result);
+    }
+}
@@ -0,0 +1,101 @@
+//! Day 2: box IDs, one per line, like `fonbsmjyqugrapsczckghtvdxl`.
+
+use rand::Rng;
+use std::collections::HashSet;
+
+/// How many box IDs there are.
+pub const DEFAULT_SIZE: usize = 250;
+
+/// How long each box ID is.
+const ID_LENGTH: usize = 26;
+
+/// Every way an ID could be one letter away from another: the ID with each
+/// of its letters in turn left out, along with where it was left out from.
+fn near_matches(id: &[u8]) -> Vec<(usize, Vec<u8>)> {
+    let mut keys = Vec::new();
+    for index in 0..id.len() {
+        let mut key = id.to_vec();
+        key.remove(index);
+        keys.push((index, key));
+    }
+    keys
+}
+
+fn random_id<R: Rng>(rng: &mut R) -> Vec<u8> {
+    (0..ID_LENGTH).map(|_| rng.gen_range(b'a'..=b'z')).collect()
+}
+
+/// Generates `size` box IDs, exactly two of which differ by a single letter.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(2);
+
+    // No two IDs may share a near match, which also keeps them all different
+    let mut seen = HashSet::new();
+    let mut ids = Vec::new();
+    while ids.len() < size - 1 {
+        let id = random_id(rng);
+        let keys = near_matches(&id);
+        if keys.iter().any(|key| seen.contains(key)) {
+            continue;
+        }
+        seen.extend(keys);
+        ids.push(id);
+    }
+
+    // Copy one of them with a letter changed, checking the copy isn't also
+    // one letter away from some other ID
+    loop {
+        let original = rng.gen_range(0..ids.len());
+        let index = rng.gen_range(0..ID_LENGTH);
+        let mut id = ids[original].clone();
+        while id[index] == ids[original][index] {
+            id[index] = rng.gen_range(b'a'..=b'z');
+        }
+
+        let keys = near_matches(&id);
+        let clashes = keys.iter()
+            .enumerate()
+            .any(|(key_index, key)| key_index != index && seen.contains(key));
+        if clashes {
+            continue;
+        }
+
+        let position = rng.gen_range(0..=ids.len());
+        ids.insert(position, id);
+        break;
+    }
+
+    let mut text = String::new();
+    for id in ids {
+        text += &String::from_utf8(id).unwrap();
+        text += "\n";
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_from_seed;
+
+    #[test]
+    fn test_one_near_match() {
+        for seed in 0..10 {
+            let text = generate(&mut rng_from_seed(seed), 100);
+            let ids: Vec<&str> = text.lines().collect();
+            assert_eq!(100, ids.len());
+
+            let mut near = 0;
+            for (index, a) in ids.iter().enumerate() {
+                for b in &ids[index + 1..] {
+                    let differences = a.chars().zip(b.chars()).filter(|(a, b)| a != b).count();
+                    assert_ne!(0, differences);
+                    if differences == 1 {
+                        near += 1;
+                    }
+                }
+            }
+            assert_eq!(1, near, "seed {}", seed);
+        }
+    }
+}
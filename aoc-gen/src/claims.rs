@@ -0,0 +1,144 @@
+//! Day 3: claims on the fabric, one per line, like `#1 @ 108,350: 22x29`.
+
+use rand::Rng;
+
+/// How many claims there are.
+pub const DEFAULT_SIZE: usize = 1_300;
+
+/// How many inches the fabric is along each side.
+const FABRIC_SIDE: usize = 1_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Claim {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Claim {
+    fn random<R: Rng>(rng: &mut R) -> Claim {
+        let width = rng.gen_range(5..=30);
+        let height = rng.gen_range(5..=30);
+        Claim {
+            x: rng.gen_range(0..=(FABRIC_SIDE - width)),
+            y: rng.gen_range(0..=(FABRIC_SIDE - height)),
+            width,
+            height,
+        }
+    }
+
+    /// A claim somewhere inside this one, which is bound to overlap it.
+    fn inside<R: Rng>(&self, rng: &mut R) -> Claim {
+        let x = rng.gen_range(self.x..(self.x + self.width));
+        let y = rng.gen_range(self.y..(self.y + self.height));
+        Claim {
+            x,
+            y,
+            width: rng.gen_range(1..=(self.x + self.width - x)),
+            height: rng.gen_range(1..=(self.y + self.height - y)),
+        }
+    }
+
+    fn overlaps(&self, other: &Claim) -> bool {
+        self.x < other.x + other.width && other.x < self.x + self.width
+            && self.y < other.y + other.height && other.y < self.y + self.height
+    }
+}
+
+/// Generates `size` claims, exactly one of which doesn't overlap any other.
+/// It takes at least three claims for that, so there are never fewer unless
+/// only one is asked for.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = if size <= 1 { 1 } else { size.max(3) };
+
+    let alone = Claim::random(rng);
+    let mut claims = Vec::new();
+    while claims.len() < size - 1 {
+        let claim = Claim::random(rng);
+        if !claim.overlaps(&alone) {
+            claims.push(claim);
+        }
+    }
+
+    // Count how many claims cover each square inch, so that claims which
+    // overlap nothing can be found
+    let mut coverage = vec![0u8; FABRIC_SIDE * FABRIC_SIDE];
+    for claim in &claims {
+        for y in claim.y..(claim.y + claim.height) {
+            for x in claim.x..(claim.x + claim.width) {
+                let count = &mut coverage[y * FABRIC_SIDE + x];
+                *count = count.saturating_add(1);
+            }
+        }
+    }
+    let mut overlapping: Vec<bool> = claims.iter()
+        .map(|claim| {
+            (claim.y..(claim.y + claim.height)).any(|y| {
+                (claim.x..(claim.x + claim.width)).any(|x| coverage[y * FABRIC_SIDE + x] > 1)
+            })
+        })
+        .collect();
+
+    // Move each claim that overlaps nothing on top of another claim. Nothing
+    // overlapped it, so moving it can't leave any other claim on its own.
+    for index in 0..claims.len() {
+        if overlapping[index] {
+            continue;
+        }
+
+        let others: Vec<usize> = (0..claims.len()).filter(|other| *other != index).collect();
+        let already_overlapping: Vec<usize> = others.iter().cloned().filter(|other| overlapping[*other]).collect();
+        let target = if already_overlapping.is_empty() {
+            others[rng.gen_range(0..others.len())]
+        } else {
+            already_overlapping[rng.gen_range(0..already_overlapping.len())]
+        };
+
+        claims[index] = claims[target].inside(rng);
+        overlapping[index] = true;
+        overlapping[target] = true;
+    }
+
+    let position = rng.gen_range(0..=claims.len());
+    claims.insert(position, alone);
+
+    let mut text = String::new();
+    for (index, claim) in claims.iter().enumerate() {
+        text += &format!("#{} @ {},{}: {}x{}\n", index + 1, claim.x, claim.y, claim.width, claim.height);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_from_seed;
+
+    fn parse(line: &str) -> Claim {
+        let numbers: Vec<usize> = line.split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .map(|number| number.parse().unwrap())
+            .collect();
+        Claim { x: numbers[1], y: numbers[2], width: numbers[3], height: numbers[4] }
+    }
+
+    #[test]
+    fn test_exactly_one_claim_alone() {
+        for seed in 0..10 {
+            for size in [1, 2, 3, 10, 200].iter() {
+                let text = generate(&mut rng_from_seed(seed), *size);
+                let claims: Vec<Claim> = text.lines().map(parse).collect();
+                assert_eq!((*size).max(if *size == 1 { 1 } else { 3 }), claims.len());
+
+                let alone = claims.iter()
+                    .enumerate()
+                    .filter(|(index, claim)| {
+                        claims.iter().enumerate().all(|(other, other_claim)| other == *index || !claim.overlaps(other_claim))
+                    })
+                    .count();
+                assert_eq!(1, alone, "seed {}, size {}", seed, size);
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Day 6: coordinates, one per line, like `69, 102`.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// How many coordinates there are.
+pub const DEFAULT_SIZE: usize = 50;
+
+/// How far across the coordinates are spread.
+const SPREAD: i32 = 400;
+
+/// Generates `size` different coordinates, at least one of which has a
+/// finite area around it. That one is fenced in by four others, each the
+/// same distance from it in a different direction: anywhere far enough away
+/// is closer to one of the fence than to it. It takes five coordinates to
+/// build the fence, so there are never fewer.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(5);
+
+    let distance = rng.gen_range(5..=30);
+    let x = rng.gen_range(distance..=(SPREAD - distance));
+    let y = rng.gen_range(distance..=(SPREAD - distance));
+    let mut coordinates = vec![
+        (x, y),
+        (x - distance, y),
+        (x + distance, y),
+        (x, y - distance),
+        (x, y + distance),
+    ];
+
+    let mut seen: HashSet<(i32, i32)> = coordinates.iter().cloned().collect();
+    while coordinates.len() < size {
+        let coordinate = (rng.gen_range(0..=SPREAD), rng.gen_range(0..=SPREAD));
+        if seen.insert(coordinate) {
+            coordinates.push(coordinate);
+        }
+    }
+
+    coordinates.shuffle(rng);
+
+    let mut text = String::new();
+    for (x, y) in coordinates {
+        text += &format!("{}, {}\n", x, y);
+    }
+    text
+}
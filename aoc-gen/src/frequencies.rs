@@ -0,0 +1,87 @@
+//! Day 1: frequency changes, one per line, like `+13` or `-8`.
+
+use rand::Rng;
+
+/// How many changes there are.
+pub const DEFAULT_SIZE: usize = 1_000;
+
+/// The most passes over the list it takes for a frequency to repeat.
+const MAX_PASSES: i64 = 5_000;
+
+/// Generates `size` changes, the last of which sets how far the frequency
+/// drifts with each pass over the list.
+///
+/// Every pass shifts each frequency along by the drift, so two frequencies
+/// a whole number of drifts apart are bound to meet. With more changes than
+/// the drift is big, two of them always are, and keeping the drift to at
+/// least the spread of frequencies over `MAX_PASSES` means they meet soon
+/// enough to be found by going round the list.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+
+    let mut changes = Vec::new();
+    let mut frequency = 0i64;
+    let mut lowest = 0i64;
+    let mut highest = 0i64;
+    for _ in 0..(size - 1) {
+        let mut change = 0;
+        while change == 0 {
+            change = rng.gen_range(-20..=20);
+        }
+        frequency += change;
+        lowest = lowest.min(frequency);
+        highest = highest.max(frequency);
+        changes.push(change);
+    }
+
+    let drift = if size == 1 {
+        // A single change has to be zero to ever repeat
+        0
+    } else {
+        let smallest = ((highest - lowest) / MAX_PASSES + 1).min(size as i64 - 1);
+        let largest = smallest.max(20.min(size as i64 - 1));
+        let drift = rng.gen_range(smallest..=largest);
+        if rng.gen_bool(0.5) { drift } else { -drift }
+    };
+    changes.push(drift - frequency);
+
+    let mut text = String::new();
+    for change in changes {
+        text += &format!("{:+}\n", change);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng_from_seed;
+    use std::collections::HashSet;
+
+    fn first_repeat(text: &str) -> Option<i64> {
+        let changes: Vec<i64> = text.lines().map(|line| line.parse().unwrap()).collect();
+        let mut seen = HashSet::new();
+        let mut frequency = 0;
+        seen.insert(frequency);
+        for _ in 0..MAX_PASSES {
+            for change in &changes {
+                frequency += change;
+                if !seen.insert(frequency) {
+                    return Some(frequency);
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_always_repeats() {
+        for seed in 0..20 {
+            for size in [1, 2, 3, 50, 1_000].iter() {
+                let text = generate(&mut rng_from_seed(seed), *size);
+                assert_eq!(*size, text.lines().count());
+                assert!(first_repeat(&text).is_some(), "seed {}, size {}", seed, size);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Day 4: a guard log, one entry per line in no particular order, like
+//! `[1518-11-01 00:05] falls asleep`.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// How many shifts the log covers.
+pub const DEFAULT_SIZE: usize = 350;
+
+const DAYS_IN_MONTH: [usize; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+fn is_leap_year(year: usize) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// The date `days` days after the 1st of January 1518.
+fn date(mut days: usize) -> String {
+    let mut year = 1518;
+    loop {
+        let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+        if days < days_in_year {
+            break;
+        }
+        days -= days_in_year;
+        year += 1;
+    }
+
+    let mut month = 0;
+    loop {
+        let days_in_month = if month == 1 && is_leap_year(year) { 29 } else { DAYS_IN_MONTH[month] };
+        if days < days_in_month {
+            break;
+        }
+        days -= days_in_month;
+        month += 1;
+    }
+
+    format!("{}-{:02}-{:02}", year, month + 1, days + 1)
+}
+
+/// Generates a log of `size` shifts, one a night, each starting a little
+/// before or just after midnight with a guard napping up to three times
+/// between then and one o'clock. At least one guard naps.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+
+    let mut guards = Vec::new();
+    for _ in 0..(size / 12).max(1) {
+        guards.push(rng.gen_range(10..=3_500));
+    }
+
+    let mut entries = Vec::new();
+    let first_day = rng.gen_range(1..=200);
+    for shift in 0..size {
+        let day = first_day + shift;
+        let guard = guards[rng.gen_range(0..guards.len())];
+
+        let mut first_minute = 0;
+        if rng.gen_bool(0.5) {
+            entries.push(format!("[{} 23:{:02}] Guard #{} begins shift", date(day - 1), rng.gen_range(45..=59), guard));
+        } else {
+            let minute = rng.gen_range(0..=3);
+            entries.push(format!("[{} 00:{:02}] Guard #{} begins shift", date(day), minute, guard));
+            first_minute = minute + 1;
+        }
+
+        // Each nap is a pair of minutes, falling asleep at the first and
+        // waking at the second
+        let naps = if shift == 0 { rng.gen_range(1..=3) } else { rng.gen_range(0..=3) };
+        let mut minutes: Vec<usize> = (first_minute..60).collect();
+        minutes.shuffle(rng);
+        minutes.truncate(naps * 2);
+        minutes.sort();
+        for nap in minutes.chunks(2) {
+            entries.push(format!("[{} 00:{:02}] falls asleep", date(day), nap[0]));
+            entries.push(format!("[{} 00:{:02}] wakes up", date(day), nap[1]));
+        }
+    }
+
+    entries.shuffle(rng);
+
+    let mut text = String::new();
+    for entry in entries {
+        text += &entry;
+        text += "\n";
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date() {
+        assert_eq!("1518-01-01", date(0));
+        assert_eq!("1518-03-01", date(59));
+        assert_eq!("1518-12-31", date(364));
+        assert_eq!("1519-01-01", date(365));
+        assert_eq!("1520-02-29", date(365 * 2 + 59));
+    }
+}
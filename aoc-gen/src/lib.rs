@@ -0,0 +1,97 @@
+//! Random puzzle inputs for every day, for trying the solutions on inputs
+//! bigger and stranger than the one checked in beside them.
+//!
+//! Every input is generated from a seed, so the same seed and size always
+//! give the same input. The size is what a day's input has a lot of: lines
+//! of frequency changes for day 1, units of polymer for day 5 and so on.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub mod box_ids;
+pub mod claims;
+pub mod coordinates;
+pub mod frequencies;
+pub mod guard_logs;
+pub mod license_tree;
+pub mod marbles;
+pub mod plants;
+pub mod polymers;
+pub mod serial_numbers;
+pub mod stars;
+pub mod steps;
+
+/// The random number generator behind every input. ChaCha's output is fixed
+/// for a seed, unlike `StdRng`'s, so seeds keep giving the same inputs.
+pub fn rng_from_seed(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// What the size counts for a day, and the size used when none is given.
+/// These defaults are roughly the size of the real inputs.
+pub fn size_of(day: u32) -> Option<(&'static str, usize)> {
+    match day {
+        1 => Some(("frequency changes", frequencies::DEFAULT_SIZE)),
+        2 => Some(("box IDs", box_ids::DEFAULT_SIZE)),
+        3 => Some(("claims", claims::DEFAULT_SIZE)),
+        4 => Some(("shifts", guard_logs::DEFAULT_SIZE)),
+        5 => Some(("units", polymers::DEFAULT_SIZE)),
+        6 => Some(("coordinates", coordinates::DEFAULT_SIZE)),
+        7 => Some(("steps, at most 26", steps::DEFAULT_SIZE)),
+        8 => Some(("nodes, roughly", license_tree::DEFAULT_SIZE)),
+        9 => Some(("the last marble", marbles::DEFAULT_SIZE)),
+        10 => Some(("letters in the message", stars::DEFAULT_SIZE)),
+        11 => Some(("nothing, it's a single number", 1)),
+        12 => Some(("pots in the initial state", plants::DEFAULT_SIZE)),
+        _ => None,
+    }
+}
+
+/// Generates an input for a day from a seed, or `None` if there's no such
+/// day. Without a size, the input is about as big as the real one.
+pub fn generate(day: u32, seed: u64, size: Option<usize>) -> Option<String> {
+    let (_, default_size) = size_of(day)?;
+    let size = size.unwrap_or(default_size);
+    let mut rng = rng_from_seed(seed);
+
+    let input = match day {
+        1 => frequencies::generate(&mut rng, size),
+        2 => box_ids::generate(&mut rng, size),
+        3 => claims::generate(&mut rng, size),
+        4 => guard_logs::generate(&mut rng, size),
+        5 => polymers::generate(&mut rng, size),
+        6 => coordinates::generate(&mut rng, size),
+        7 => steps::generate(&mut rng, size),
+        8 => license_tree::generate(&mut rng, size),
+        9 => marbles::generate(&mut rng, size),
+        10 => stars::generate(&mut rng, size),
+        11 => serial_numbers::generate(&mut rng),
+        12 => plants::generate(&mut rng, size),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for day in 1..=12 {
+            assert_eq!(generate(day, 7, Some(20)), generate(day, 7, Some(20)), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_different_seeds_different_inputs() {
+        assert_ne!(generate(1, 1, None), generate(1, 2, None));
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert_eq!(None, generate(0, 1, None));
+        assert_eq!(None, generate(25, 1, None));
+    }
+}
@@ -0,0 +1,40 @@
+//! Day 8: a license tree, a single line of numbers like
+//! `2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2`.
+
+use rand::Rng;
+
+/// Roughly how many nodes the tree has.
+pub const DEFAULT_SIZE: usize = 1_500;
+
+/// How deep the tree can go, so the recursive solutions don't run out of
+/// stack.
+const MAX_DEPTH: usize = 12;
+
+/// Writes out a node and its children, taking one from `budget` for each
+/// child.
+fn node<R: Rng>(rng: &mut R, budget: &mut usize, depth: usize, numbers: &mut Vec<u32>) {
+    let most_children = if depth >= MAX_DEPTH { 0 } else { (*budget).min(7) };
+    let children = rng.gen_range(0..=most_children);
+    *budget -= children;
+    let metadata = rng.gen_range(1..=11);
+
+    numbers.push(children as u32);
+    numbers.push(metadata);
+    for _ in 0..children {
+        node(rng, budget, depth + 1, numbers);
+    }
+    for _ in 0..metadata {
+        numbers.push(rng.gen_range(1..=9));
+    }
+}
+
+/// Generates a tree of at most `size` nodes. Metadata entries run from 1 to
+/// 9, so some refer to children a node has and some to ones it doesn't.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut budget = size.max(1) - 1;
+    let mut numbers = Vec::new();
+    node(rng, &mut budget, 0, &mut numbers);
+
+    let numbers: Vec<String> = numbers.iter().map(|number| number.to_string()).collect();
+    numbers.join(" ") + "\n"
+}
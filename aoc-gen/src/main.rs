@@ -0,0 +1,117 @@
+use std::env;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "\
+Usage:
+    aoc-gen --day <day> [--seed <seed>] [--size <size>]
+
+Prints a random puzzle input for a day. The same seed and size always give
+the same input. Without a seed one is picked, and printed to standard error
+so the input can be made again.
+
+What the size counts, and its default, depends on the day:";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, usage());
+            process::exit(2);
+        }
+    };
+
+    let seed = match options.seed {
+        Some(seed) => seed,
+        None => {
+            let seed = random_seed();
+            eprintln!("Seed: {}", seed);
+            seed
+        },
+    };
+
+    match aoc_gen::generate(options.day, seed, options.size) {
+        Some(input) => print!("{}", input),
+        None => {
+            eprintln!("There is no day {}", options.day);
+            process::exit(1);
+        },
+    }
+}
+
+fn usage() -> String {
+    let mut usage = String::from(USAGE);
+    for day in 1..=12 {
+        if let Some((counts, default_size)) = aoc_gen::size_of(day) {
+            usage += &format!("\n    {:>2}  {} ({})", day, counts, default_size);
+        }
+    }
+    usage
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    day: u32,
+    seed: Option<u64>,
+    size: Option<usize>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut day = None;
+    let mut seed = None;
+    let mut size = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number(arg, args.next())?),
+            "--seed" => seed = Some(parse_number(arg, args.next())?),
+            "--size" => size = Some(parse_number(arg, args.next())?),
+            other => return Err(format!("Unknown option '{}'", other)),
+        }
+    }
+
+    match day {
+        Some(day) => Ok(Options { day, seed, size }),
+        None => Err(String::from("Expected a --day")),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {}", option))?;
+    value.parse().map_err(|_| format!("Expected a number for {}, got '{}'", option, value))
+}
+
+/// A seed for when none is given, from the time.
+fn random_seed() -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    now.as_secs() ^ now.subsec_nanos() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&["--day", "3", "--seed", "42", "--size", "10"])).unwrap();
+        assert_eq!(Options { day: 3, seed: Some(42), size: Some(10) }, options);
+
+        let options = parse_args(&args(&["--day", "12"])).unwrap();
+        assert_eq!(Options { day: 12, seed: None, size: None }, options);
+    }
+
+    #[test]
+    fn test_parse_bad_args() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["--seed", "1"])).is_err());
+        assert!(parse_args(&args(&["--day", "three"])).is_err());
+        assert!(parse_args(&args(&["--day", "3", "--size"])).is_err());
+        assert!(parse_args(&args(&["--day", "3", "--colour", "red"])).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! Day 9: a marble game, like `459 players; last marble is worth 72103 points`.
+
+use rand::Rng;
+
+/// The last marble played.
+pub const DEFAULT_SIZE: usize = 72_000;
+
+/// Generates a game for up to 500 players that ends with marble `size`.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let players = rng.gen_range(1..=500);
+    format!("{} players; last marble is worth {} points\n", players, size.max(1))
+}
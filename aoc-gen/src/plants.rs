@@ -0,0 +1,115 @@
+//! Day 12: an initial state of pots followed by the rules for how plants
+//! spread, like
+//!
+//! ```text
+//! initial state: #..#.#..##......###...###
+//!
+//! ...## => #
+//! ..#.. => #
+//! ```
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// How many pots are in the initial state.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// How many generations the plants are given to settle down.
+const GENERATIONS_TO_SETTLE: usize = 1_000;
+
+/// The plants after one generation. Pots are kept from the first to the
+/// last plant, with how far along the first one is.
+fn step(pots: &[bool], first: i64, rules: &[bool]) -> (Vec<bool>, i64) {
+    let pot = |index: i64| index >= 0 && (index as usize) < pots.len() && pots[index as usize];
+
+    let mut next = Vec::new();
+    for index in -2..(pots.len() as i64 + 2) {
+        let mut pattern = 0;
+        for offset in -2..=2 {
+            pattern = (pattern << 1) | pot(index + offset) as usize;
+        }
+        next.push(rules[pattern]);
+    }
+
+    trim(next, first - 2)
+}
+
+fn trim(mut pots: Vec<bool>, mut first: i64) -> (Vec<bool>, i64) {
+    while pots.last() == Some(&false) {
+        pots.pop();
+    }
+    let leading = pots.iter().take_while(|pot| !**pot).count();
+    pots.drain(..leading);
+    first += leading as i64;
+    (pots, first)
+}
+
+/// Whether the plants settle into a pattern that only ever moves along,
+/// dying out included, so that the sum of their pots grows steadily.
+fn settles(pots: &[bool], rules: &[bool]) -> bool {
+    let (mut pots, mut first) = trim(pots.to_vec(), 0);
+    for _ in 0..GENERATIONS_TO_SETTLE {
+        let (next, next_first) = step(&pots, first, rules);
+        if next == pots {
+            return true;
+        }
+        pots = next;
+        first = next_first;
+    }
+    false
+}
+
+/// Generates an initial state `size` pots long and a rule for every
+/// pattern. Empty pots never grow a plant, or every pot would fill up, and
+/// the rules are picked so that the plants settle down rather than growing
+/// chaotically forever.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+
+    let pots: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.5)).collect();
+    let rules = loop {
+        let mut rules: Vec<bool> = (0..32).map(|_| rng.gen_bool(0.5)).collect();
+        rules[0] = false;
+        if settles(&pots, &rules) {
+            break rules;
+        }
+    };
+
+    let mut text = String::from("initial state: ");
+    for pot in &pots {
+        text.push(if *pot { '#' } else { '.' });
+    }
+    text += "\n\n";
+
+    let mut lines = Vec::new();
+    for (pattern, result) in rules.iter().enumerate() {
+        let mut line = String::new();
+        for bit in (0..5).rev() {
+            line.push(if pattern & (1 << bit) != 0 { '#' } else { '.' });
+        }
+        line += if *result { " => #\n" } else { " => .\n" };
+        lines.push(line);
+    }
+    lines.shuffle(rng);
+
+    text + &lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glider_settles() {
+        // Every plant moves one pot to the right
+        let rules: Vec<bool> = (0..32).map(|pattern| pattern & 0b01000 != 0).collect();
+        assert!(settles(&[true, false, true, true], &rules));
+    }
+
+    #[test]
+    fn test_growth_never_settles() {
+        // Plants spread out in both directions forever
+        let rules: Vec<bool> = (0..32).map(|pattern| pattern & 0b01110 != 0).collect();
+        assert!(!settles(&[true], &rules));
+    }
+}
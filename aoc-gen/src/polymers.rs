@@ -0,0 +1,38 @@
+//! Day 5: a polymer, a single line of units like `dabAcCaCBAcCcaDA`.
+
+use rand::Rng;
+
+/// How many units long the polymer is.
+pub const DEFAULT_SIZE: usize = 50_000;
+
+/// Generates a polymer `size` units long. Units are often followed by their
+/// opposite, so that reacting one pair brings others together.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+
+    let mut polymer = String::new();
+    let mut last = None;
+    for _ in 0..size {
+        let unit = match last {
+            Some(unit) if rng.gen_bool(0.3) => opposite(unit),
+            _ => {
+                let unit = rng.gen_range('a'..='z');
+                if rng.gen_bool(0.5) { unit.to_ascii_uppercase() } else { unit }
+            },
+        };
+        polymer.push(unit);
+        last = Some(unit);
+    }
+
+    polymer.push('\n');
+    polymer
+}
+
+/// The same type of unit with the opposite polarity.
+fn opposite(unit: char) -> char {
+    if unit.is_ascii_lowercase() {
+        unit.to_ascii_uppercase()
+    } else {
+        unit.to_ascii_lowercase()
+    }
+}
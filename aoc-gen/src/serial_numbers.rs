@@ -0,0 +1,8 @@
+//! Day 11: a grid serial number, like `2568`.
+
+use rand::Rng;
+
+/// Generates a serial number. Any will do, so there's nothing to size.
+pub fn generate<R: Rng>(rng: &mut R) -> String {
+    format!("{}\n", rng.gen_range(1..=9_999))
+}
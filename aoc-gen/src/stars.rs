@@ -0,0 +1,111 @@
+//! Day 10: stars, one per line, like
+//! `position=< 9,  1> velocity=< 0,  2>`.
+
+use rand::Rng;
+
+/// How many letters are in the message.
+pub const DEFAULT_SIZE: usize = 8;
+
+/// The letters the message can be made of, in the style of the puzzle's.
+const FONT: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// How far apart the letters start, leaving a gap of two between them.
+const LETTER_SPACING: i64 = 8;
+
+struct Star {
+    x: i64,
+    y: i64,
+    dx: i64,
+    dy: i64,
+}
+
+/// The area of the box around the stars after `second` seconds.
+fn area(stars: &[Star], second: i64) -> i64 {
+    let mut left = i64::MAX;
+    let mut right = i64::MIN;
+    let mut top = i64::MAX;
+    let mut bottom = i64::MIN;
+    for star in stars {
+        let x = star.x + star.dx * second;
+        let y = star.y + star.dy * second;
+        left = left.min(x);
+        right = right.max(x);
+        top = top.min(y);
+        bottom = bottom.max(y);
+    }
+    (right - left) * (bottom - top)
+}
+
+/// The second the stars stop closing in, found the same way the solution
+/// finds it.
+fn second_aligned(stars: &[Star]) -> i64 {
+    let mut second = 1;
+    while area(stars, second + 1) <= area(stars, second) {
+        second += 1;
+    }
+    second
+}
+
+/// Generates the stars for a message `size` letters long. Each lit point of
+/// the message has a star, now and then two, and they all arrive at once
+/// after somewhere between 5,000 and 15,000 seconds.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(1);
+
+    let left = rng.gen_range(100..=200);
+    let top = rng.gen_range(100..=200);
+    let mut points = Vec::new();
+    for letter in 0..size {
+        let (_, glyph) = FONT[rng.gen_range(0..FONT.len())];
+        for (row, line) in glyph.iter().enumerate() {
+            for (column, pixel) in line.chars().enumerate() {
+                if pixel != '#' {
+                    continue;
+                }
+                let point = (left + letter as i64 * LETTER_SPACING + column as i64, top + row as i64);
+                points.push(point);
+                if rng.gen_bool(0.1) {
+                    points.push(point);
+                }
+            }
+        }
+    }
+
+    // Run the stars backwards from the message, trying again in the rare
+    // case they happen to line up better at some other time
+    let seconds = rng.gen_range(5_000..=15_000);
+    let stars = loop {
+        let stars: Vec<Star> = points.iter()
+            .map(|(x, y)| {
+                let dx = rng.gen_range(-5..=5);
+                let dy = rng.gen_range(-5..=5);
+                Star { x: x - dx * seconds, y: y - dy * seconds, dx, dy }
+            })
+            .collect();
+        if second_aligned(&stars) == seconds {
+            break stars;
+        }
+    };
+
+    let mut text = String::new();
+    for star in stars {
+        text += &format!("position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>\n", star.x, star.y, star.dx, star.dy);
+    }
+    text
+}
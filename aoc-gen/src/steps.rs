@@ -0,0 +1,38 @@
+//! Day 7: step dependencies, one per line, like
+//! `Step C must be finished before step A can begin.`
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeSet;
+
+/// How many steps there are. Steps are named by letter, so there can be no
+/// more than 26.
+pub const DEFAULT_SIZE: usize = 26;
+
+/// Generates the dependencies between `size` steps. The steps are put in a
+/// random order and each depends on at least one step before it, so they
+/// can always be completed and every step is named by some dependency.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.clamp(2, 26);
+
+    let mut letters: Vec<char> = ('A'..='Z').collect();
+    letters.shuffle(rng);
+    letters.truncate(size);
+
+    let mut dependencies = BTreeSet::new();
+    for after in 1..size {
+        dependencies.insert((rng.gen_range(0..after), after));
+        for before in 0..after {
+            if rng.gen_bool(0.2) {
+                dependencies.insert((before, after));
+            }
+        }
+    }
+
+    let mut lines: Vec<String> = dependencies.iter()
+        .map(|(before, after)| format!("Step {} must be finished before step {} can begin.\n", letters[*before], letters[*after]))
+        .collect();
+    lines.shuffle(rng);
+
+    lines.concat()
+}
@@ -0,0 +1,116 @@
+use aoc::solutions;
+use aoc_common::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::fs;
+
+/// How many times larger the scaled inputs are than the checked-in ones.
+const SCALE_FACTORS: [usize; 3] = [1, 2, 4];
+
+/// The seed for the days whose scaled inputs are generated.
+const SEED: u64 = 1;
+
+/// Every solution, including the variants, sorted by day and part.
+fn all_solutions() -> Vec<Box<dyn Solution>> {
+    let mut all = solutions::solutions();
+    all.extend(solutions::variants());
+    all.sort_by_key(|solution| (solution.day(), solution.part()));
+    all
+}
+
+fn bench_name(solution: &dyn Solution) -> String {
+    format!("part {}/{}", solution.part(), solution.name())
+}
+
+fn read_input(solution: &dyn Solution) -> String {
+    fs::read_to_string(solution.input_path())
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", solution.input_path(), err))
+}
+
+/// Makes a day's input `factor` times larger while keeping it a puzzle the
+/// solutions can still solve. Days whose inputs can't simply be repeated are
+/// generated instead, `factor` times the size `aoc-gen` makes by default.
+/// Day 7 isn't scaled, as its steps are named by letter so there can't be
+/// more than the 26 there already are, and nor is day 11, whose input is a
+/// single serial number for a grid that's always 300 by 300.
+fn scale_input(day: u32, input: &str, factor: usize) -> Option<String> {
+    match day {
+        // One item per line, so more lines is just a longer list
+        1 | 2 | 10 => {
+            let lines: Vec<&str> = aoc_common::lines(input).collect();
+            let mut scaled = String::new();
+            for _ in 0..factor {
+                for line in &lines {
+                    scaled += line;
+                    scaled += "\n";
+                }
+            }
+            Some(scaled)
+        },
+        // A single polymer, which can be repeated end to end
+        5 => Some(input.trim().repeat(factor)),
+        // A single game, played on to a higher marble
+        9 => {
+            let words: Vec<&str> = input.split_whitespace().collect();
+            let last_marble: usize = words[6].parse().ok()?;
+            Some(format!("{} players; last marble is worth {} points", words[0], last_marble * factor))
+        },
+        // Claims, shifts, coordinates, tree nodes and pots need to stay
+        // consistent with each other, so a bigger input is a new one
+        3 | 4 | 6 | 8 | 12 => {
+            let (_, default_size) = aoc_gen::size_of(day)?;
+            aoc_gen::generate(day, SEED, Some(default_size * factor))
+        },
+        _ => None,
+    }
+}
+
+/// Solves every part of every day against the input checked in beside it.
+/// Each day is its own group, so solutions to the same part are reported
+/// side by side.
+fn bench_checked_in_inputs(c: &mut Criterion) {
+    let all = all_solutions();
+    for day in 1..=12 {
+        let mut group = c.benchmark_group(format!("day {:02}", day));
+        group.sample_size(10);
+
+        for solution in all.iter().filter(|solution| solution.day() == day) {
+            let input = read_input(solution.as_ref());
+            group.bench_function(bench_name(solution.as_ref()), |b| {
+                b.iter(|| solution.solve(&input).unwrap())
+            });
+        }
+
+        group.finish();
+    }
+}
+
+/// Solves the days that can be scaled against ever larger inputs, to show
+/// how each solution grows with its input.
+fn bench_scaled_inputs(c: &mut Criterion) {
+    let all = all_solutions();
+    for day in 1..=12 {
+        let mut group = c.benchmark_group(format!("day {:02} scaled", day));
+        group.sample_size(10);
+
+        for solution in all.iter().filter(|solution| solution.day() == day) {
+            let input = read_input(solution.as_ref());
+            for factor in SCALE_FACTORS.iter() {
+                let scaled = match scale_input(day, &input, *factor) {
+                    Some(scaled) => scaled,
+                    None => continue,
+                };
+
+                group.throughput(Throughput::Bytes(scaled.len() as u64));
+                let id = BenchmarkId::new(bench_name(solution.as_ref()), factor);
+                group.bench_with_input(id, &scaled, |b, scaled| {
+                    b.iter(|| solution.solve(scaled).unwrap())
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_checked_in_inputs, bench_scaled_inputs);
+criterion_main!(benches);
@@ -0,0 +1,182 @@
+//! The known answers to each checked-in puzzle input, kept in an
+//! `answers.json` beside the `input.txt`:
+//!
+//! ```json
+//! {
+//!   "input_checksum": "fnv1a64:e41c9c708bc79ab4",
+//!   "part1": 574,
+//!   "part2": 452
+//! }
+//! ```
+//!
+//! The checksum is of the input the answers were found for, so a changed
+//! input isn't mistaken for a broken solution.
+
+use crate::report;
+use aoc_common::{Answer, Solution};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the answers to a solution's checked-in input live.
+pub fn answers_path(solution: &dyn Solution) -> PathBuf {
+    Path::new(solution.input_path()).with_file_name("answers.json")
+}
+
+/// The answers recorded for one input.
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+    pub input_checksum: String,
+    part1: Option<Value>,
+    part2: Option<Value>,
+}
+
+impl Answers {
+    pub fn from_json(text: &str) -> Result<Answers, String> {
+        let json: Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+        let input_checksum = match json["input_checksum"].as_str() {
+            Some(checksum) => checksum.to_string(),
+            None => return Err(String::from("missing input_checksum")),
+        };
+
+        Ok(Answers {
+            input_checksum,
+            part1: json.get("part1").cloned(),
+            part2: json.get("part2").cloned(),
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Answers, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        Answers::from_json(&text)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+    }
+
+    /// The answer to a part, if one has been recorded.
+    pub fn part(&self, part: u32) -> Option<&Value> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Checks an answer against the recorded one, describing how they differ
+/// if they don't match.
+pub fn check(expected: &Value, actual: &Answer) -> Result<(), String> {
+    let actual = report::answer_to_json(actual);
+    if *expected == actual {
+        return Ok(());
+    }
+
+    Err(diff(&show(expected), &show(&actual)))
+}
+
+/// Solves a solution's checked-in input and checks the answer against the
+/// one recorded for it.
+pub fn verify(solution: &dyn Solution) -> Result<(), String> {
+    let answers = Answers::read(&answers_path(solution))?;
+    let expected = match answers.part(solution.part()) {
+        Some(expected) => expected,
+        None => return Err(String::from("no answer has been recorded")),
+    };
+
+    let input = fs::read_to_string(solution.input_path())
+        .map_err(|err| format!("Failed to read {}: {}", solution.input_path(), err))?;
+    let checksum = report::checksum(&input);
+    if checksum != answers.input_checksum {
+        return Err(format!("the input has changed since its answers were recorded, from {} to {}", answers.input_checksum, checksum));
+    }
+
+    let actual = solution.solve(&input).map_err(|err| err.to_string())?;
+    check(expected, &actual)
+        .map_err(|diff| format!("the answer has changed\n{}", diff))
+}
+
+/// Shows an answer as it would be printed, without JSON's quotes.
+fn show(answer: &Value) -> String {
+    match answer.as_str() {
+        Some(text) => text.to_string(),
+        None => answer.to_string(),
+    }
+}
+
+/// A line by line diff of two answers. Lines only in the expected answer
+/// start with `-`, lines only in the actual answer with `+`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    let mut output = Vec::new();
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => output.push(format!("  {}", expected)),
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    output.push(format!("- {}", expected));
+                }
+                if let Some(actual) = actual {
+                    output.push(format!("+ {}", actual));
+                }
+            },
+        }
+    }
+
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_json() {
+        let answers = Answers::from_json(r#"{"input_checksum": "fnv1a64:0", "part1": 574, "part2": "CABDFE"}"#).unwrap();
+        assert_eq!("fnv1a64:0", answers.input_checksum);
+        assert_eq!(Some(&json!(574)), answers.part(1));
+        assert_eq!(Some(&json!("CABDFE")), answers.part(2));
+        assert_eq!(None, answers.part(3));
+    }
+
+    #[test]
+    fn test_from_json_missing_parts() {
+        let answers = Answers::from_json(r#"{"input_checksum": "fnv1a64:0"}"#).unwrap();
+        assert_eq!(None, answers.part(1));
+
+        assert!(Answers::from_json(r#"{"part1": 574}"#).is_err());
+        assert!(Answers::from_json("part1: 574").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Ok(()), check(&json!(574), &Answer::from(574)));
+        assert_eq!(Err(String::from("- 574\n+ 575")), check(&json!(574), &Answer::from(575)));
+
+        // A number and the same digits as text are different answers
+        assert!(check(&json!("574"), &Answer::from(574)).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = "*  *\n****\n*  *\n";
+        let actual = "*  *\n*** \n*  *\n*  *\n";
+        assert_eq!("  *  *\n- ****\n+ *** \n  *  *\n+ *  *", diff(expected, actual));
+    }
+
+    #[test]
+    fn test_verify() {
+        let solution = crate::solutions::find(1, 1, None).unwrap();
+        assert_eq!(Ok(()), verify(solution.as_ref()));
+    }
+
+    #[test]
+    fn test_checked_in_answers() {
+        for solution in crate::solutions::solutions() {
+            let answers = Answers::read(&answers_path(solution.as_ref())).unwrap();
+            assert!(answers.part(solution.part()).is_some(), "{}", solution.name());
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Every day's solutions gathered in one place, for the runner and the
+//! benchmarks to share.
+
+pub mod answers;
+pub mod report;
+pub mod solutions;
@@ -0,0 +1,327 @@
+use aoc::{answers, report, solutions};
+use aoc_common::{Answer, InputSource, Solution};
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+const USAGE: &str = "\
+Usage:
+    aoc run --day <day> [--part <part>] [--input <input>] [--variant <name>] [--format <format>]
+    aoc run --all [--format <format>]
+    aoc verify --day <day> [--part <part>] [--variant <name>]
+    aoc verify --all
+
+The input can be a file, a directory holding an input.txt, - for standard
+input, or for days 9 and 11 the puzzle input itself.
+
+The format is text, the default, or json for one JSON object per line.
+
+verify checks the answers to each checked-in input.txt against the ones
+recorded beside it in answers.json.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let outcome = match command {
+        Command::Run(options) => run(&options),
+        Command::Verify(options) => verify(&options),
+    };
+
+    if let Err(message) = outcome {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(RunOptions),
+    Verify(RunOptions),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct RunOptions {
+    all: bool,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    variant: Option<String>,
+    format: Format,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+    let command = match args.next().map(|arg| arg.as_str()) {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some(other) => return Err(format!("Unknown command '{}'", other)),
+        None => return Err(String::from("No command given")),
+    };
+
+    let mut options = RunOptions::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => options.all = true,
+            "--day" => options.day = Some(parse_number(arg, args.next())?),
+            "--part" => options.part = Some(parse_number(arg, args.next())?),
+            "--input" => options.input = Some(option_value(arg, args.next())?.clone()),
+            "--variant" => options.variant = Some(option_value(arg, args.next())?.clone()),
+            "--format" => options.format = parse_format(arg, args.next())?,
+            other => return Err(format!("Unknown option '{}'", other)),
+        }
+    }
+
+    if options.all == options.day.is_some() {
+        return Err(String::from("Expected exactly one of --all or --day"));
+    }
+    if options.all && (options.part.is_some() || options.input.is_some() || options.variant.is_some()) {
+        return Err(String::from("--part, --input and --variant need a --day"));
+    }
+    if let Some(part) = options.part {
+        if part != 1 && part != 2 {
+            return Err(format!("There is no part {}", part));
+        }
+    }
+
+    let command = command(options);
+    if let Command::Verify(ref options) = command {
+        if options.input.is_some() || options.format != Format::Text {
+            return Err(String::from("verify only checks the checked-in inputs, so takes no --input or --format"));
+        }
+    }
+
+    Ok(command)
+}
+
+fn option_value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", option))
+}
+
+fn parse_number(option: &str, value: Option<&String>) -> Result<u32, String> {
+    let value = option_value(option, value)?;
+    value.parse().map_err(|_| format!("Expected a number for {}, got '{}'", option, value))
+}
+
+fn parse_format(option: &str, value: Option<&String>) -> Result<Format, String> {
+    match option_value(option, value)?.as_str() {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        other => Err(format!("Unknown format '{}', expected text or json", other)),
+    }
+}
+
+fn run(options: &RunOptions) -> Result<(), String> {
+    // Carry on past solutions that fail so one bad input doesn't hide the
+    // answers to every other day
+    let mut failures = 0;
+    let solutions = selected_solutions(options)?;
+
+    // An --input is always for a single day, so read it once up front for
+    // both parts to share. Standard input can't be read twice.
+    let given_input = match options.input {
+        Some(ref arg) => {
+            let source = InputSource::from_arg(arg, solutions[0].accepts_inline_input());
+            Some((read_input(&source)?, source))
+        },
+        None => None,
+    };
+
+    for solution in solutions {
+        let (input, source) = match given_input {
+            Some((ref input, ref source)) => (input.clone(), source.clone()),
+            None => {
+                let source = InputSource::File(PathBuf::from(solution.input_path()));
+                (read_input(&source)?, source)
+            },
+        };
+
+        let start = Instant::now();
+        let result = solution.solve(&input);
+        let elapsed = start.elapsed();
+
+        if result.is_err() {
+            failures += 1;
+        }
+
+        match options.format {
+            Format::Text => match result {
+                Ok(answer) => print_answer(solution.as_ref(), &answer),
+                Err(error) => eprintln!("Day {}, part {} failed on input from {}\n{}", solution.day(), solution.part(), source, error),
+            },
+            Format::Json => {
+                let checksum = report::checksum(&input);
+                println!("{}", report::to_json(solution.as_ref(), &result, elapsed, &checksum));
+            },
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} solution(s) failed", failures));
+    }
+
+    Ok(())
+}
+
+fn verify(options: &RunOptions) -> Result<(), String> {
+    let mut failures = 0;
+    for solution in selected_solutions(options)? {
+        match answers::verify(solution.as_ref()) {
+            Ok(()) => println!("Day {}, part {}: ok", solution.day(), solution.part()),
+            Err(message) => {
+                println!("Day {}, part {} ({}): {}", solution.day(), solution.part(), solution.name(), message);
+                failures += 1;
+            },
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} solution(s) failed verification", failures));
+    }
+
+    Ok(())
+}
+
+fn read_input(source: &InputSource) -> Result<String, String> {
+    source.read().map_err(|err| format!("Failed to read {}: {}", source, err))
+}
+
+fn selected_solutions(options: &RunOptions) -> Result<Vec<Box<dyn Solution>>, String> {
+    let day = match options.day {
+        Some(day) => day,
+        None => return Ok(solutions::solutions()),
+    };
+
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let variant = options.variant.as_deref();
+    let selected: Vec<Box<dyn Solution>> = parts.iter()
+        .filter_map(|part| solutions::find(day, *part, variant))
+        .collect();
+
+    if selected.is_empty() {
+        return Err(match variant {
+            Some(name) => format!("No solution named '{}' for day {}", name, day),
+            None => format!("No solution for day {}", day),
+        });
+    }
+
+    Ok(selected)
+}
+
+fn print_answer(solution: &dyn Solution, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("Day {}, part {}:\n{}", solution.day(), solution.part(), answer);
+    } else {
+        println!("Day {}, part {}: {}", solution.day(), solution.part(), answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run_day() {
+        let result = parse_args(&args("run --day 9 --part 2 --input path")).unwrap();
+        let expected = RunOptions {
+            day: Some(9),
+            part: Some(2),
+            input: Some(String::from("path")),
+            ..RunOptions::default()
+        };
+        assert_eq!(Command::Run(expected), result);
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        let result = parse_args(&args("run --all")).unwrap();
+        let expected = RunOptions { all: true, ..RunOptions::default() };
+        assert_eq!(Command::Run(expected), result);
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let result = parse_args(&args("run --all --format json")).unwrap();
+        let expected = RunOptions { all: true, format: Format::Json, ..RunOptions::default() };
+        assert_eq!(Command::Run(expected), result);
+
+        assert!(parse_args(&args("run --all --format yaml")).is_err());
+        assert!(parse_args(&args("run --all --format")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        let result = parse_args(&args("verify --day 9 --part 2")).unwrap();
+        let expected = RunOptions { day: Some(9), part: Some(2), ..RunOptions::default() };
+        assert_eq!(Command::Verify(expected), result);
+
+        assert!(parse_args(&args("verify")).is_err());
+        assert!(parse_args(&args("verify --day 9 --input path")).is_err());
+        assert!(parse_args(&args("verify --all --format json")).is_err());
+    }
+
+    #[test]
+    fn test_parse_needs_all_or_day() {
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run --all --day 1")).is_err());
+        assert!(parse_args(&args("run --all --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_values() {
+        assert!(parse_args(&args("run --day nine")).is_err());
+        assert!(parse_args(&args("run --day 9 --part 3")).is_err());
+        assert!(parse_args(&args("run --day")).is_err());
+        assert!(parse_args(&args("run --day 9 --fast")).is_err());
+        assert!(parse_args(&args("walk --day 9")).is_err());
+    }
+
+    #[test]
+    fn test_selected_solutions_for_a_day() {
+        let options = RunOptions { day: Some(3), ..RunOptions::default() };
+        let result = selected_solutions(&options).unwrap();
+        let names: Vec<&str> = result.iter().map(|solution| solution.name()).collect();
+        assert_eq!(vec!["overlaps", "no-overlaps"], names);
+    }
+
+    #[test]
+    fn test_selected_solutions_for_a_variant() {
+        let options = RunOptions {
+            day: Some(11),
+            variant: Some(String::from("chronal-charge-deluxe")),
+            ..RunOptions::default()
+        };
+        let result = selected_solutions(&options).unwrap();
+        assert_eq!(1, result.len());
+        assert_eq!(2, result[0].part());
+    }
+
+    #[test]
+    fn test_selected_solutions_for_unknown_day() {
+        let options = RunOptions { day: Some(25), ..RunOptions::default() };
+        assert!(selected_solutions(&options).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use aoc_common::{Answer, Error, Solution};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// A checksum of a puzzle input, so that answers can be matched up with the
+/// input they were found for. This is 64 bit FNV-1a, written out in hex.
+pub fn checksum(input: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("fnv1a64:{:016x}", hash)
+}
+
+/// An answer as JSON, keeping numbers as numbers.
+pub fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(number) => json!(number),
+        Answer::Text(text) => json!(text),
+    }
+}
+
+/// Describes one run of a solution for tooling to collect. A failed run has
+/// an `error` in place of the `answer`.
+pub fn to_json(solution: &dyn Solution, result: &Result<Answer, Error>, elapsed: Duration, checksum: &str) -> Value {
+    let mut report = json!({
+        "day": solution.day(),
+        "part": solution.part(),
+        "solution": solution.name(),
+        "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+        "input_checksum": checksum,
+    });
+
+    match result {
+        Ok(answer) => report["answer"] = answer_to_json(answer),
+        Err(error) => report["error"] = json!(error.to_string()),
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!("fnv1a64:cbf29ce484222325", checksum(""));
+        assert_eq!("fnv1a64:af63dc4c8601ec8c", checksum("a"));
+        assert_ne!(checksum("+1\n-2\n"), checksum("+1\n-3\n"));
+    }
+
+    #[test]
+    fn test_answers_keep_their_type() {
+        assert_eq!(json!(1265), answer_to_json(&Answer::from(1265)));
+        assert_eq!(json!("21,68"), answer_to_json(&Answer::from("21,68")));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let solution = crate::solutions::find(1, 1, None).unwrap();
+        let result = Ok(Answer::from(3));
+        let report = to_json(solution.as_ref(), &result, Duration::from_millis(2), "fnv1a64:0");
+
+        let expected = json!({
+            "day": 1,
+            "part": 1,
+            "solution": "calibrator",
+            "answer": 3,
+            "elapsed_ms": 2.0,
+            "input_checksum": "fnv1a64:0",
+        });
+        assert_eq!(expected, report);
+    }
+
+    #[test]
+    fn test_to_json_error() {
+        let solution = crate::solutions::find(1, 1, None).unwrap();
+        let result = Err(Error::Invalid(String::from("no frequencies")));
+        let report = to_json(solution.as_ref(), &result, Duration::from_millis(2), "fnv1a64:0");
+
+        assert_eq!(json!("Invalid input: no frequencies"), report["error"]);
+        assert!(report.get("answer").is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use aoc_common::Solution;
+
+/// The solution used for each part of each day, in order.
+pub fn solutions() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new(calibrator::Part1),
+        Box::new(looping_calibrator::Part2),
+        Box::new(ims::Part1),
+        Box::new(find::Part2),
+        Box::new(overlaps::Part1),
+        Box::new(no_overlaps::Part2),
+        Box::new(repose::Part1),
+        Box::new(sleepiest_minute::Part2),
+        Box::new(reducer::Part1),
+        Box::new(good_better_bestest::Part2),
+        Box::new(chronal_manhattan::Part1),
+        Box::new(chronal_manhattan_sum::Part2),
+        Box::new(some_assembly_required::Part1),
+        Box::new(time_assembly_required::Part2),
+        Box::new(mem_man::Part1),
+        Box::new(root_value::Part2),
+        Box::new(marbles::Part1),
+        Box::new(marbles_100::Part2),
+        Box::new(stars::Part1),
+        Box::new(stars::Part2),
+        Box::new(chronal_charge::Part1),
+        Box::new(chronal_charge_deluxe_royale::Part2),
+        Box::new(underground_gol::Part1),
+        Box::new(underground_gol_betterer::Part2),
+    ]
+}
+
+/// Slower solutions to parts `solutions` already covers, kept around so
+/// they can be compared against the ones that replaced them.
+pub fn variants() -> Vec<Box<dyn Solution>> {
+    vec![
+        Box::new(chronal_charge_deluxe::Part2),
+    ]
+}
+
+/// Finds the solution for a part of a day. A variant can be picked out by
+/// crate name, otherwise the usual solution is returned.
+pub fn find(day: u32, part: u32, variant: Option<&str>) -> Option<Box<dyn Solution>> {
+    let mut candidates = solutions();
+    candidates.extend(variants());
+
+    candidates.into_iter().find(|solution| {
+        solution.day() == day && solution.part() == part && match variant {
+            Some(name) => solution.name() == name,
+            None => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_part_of_every_day_is_solved_once() {
+        let solutions = solutions();
+        assert_eq!(24, solutions.len());
+
+        for (index, solution) in solutions.iter().enumerate() {
+            let day = (index / 2 + 1) as u32;
+            let part = (index % 2 + 1) as u32;
+            assert_eq!((day, part), (solution.day(), solution.part()), "{}", solution.name());
+        }
+    }
+
+    #[test]
+    fn test_find_prefers_the_usual_solution() {
+        let solution = find(11, 2, None).unwrap();
+        assert_eq!("chronal-charge-deluxe-royale", solution.name());
+    }
+
+    #[test]
+    fn test_find_variant() {
+        let solution = find(11, 2, Some("chronal-charge-deluxe")).unwrap();
+        assert_eq!("chronal-charge-deluxe", solution.name());
+
+        assert!(find(11, 2, Some("marbles")).is_none());
+    }
+
+    #[test]
+    fn test_find_unknown_day() {
+        assert!(find(13, 1, None).is_none());
+    }
+}
@@ -0,0 +1,445 @@
+//! Differential tests for the days solved more than once. Each pair of
+//! variants is run against random inputs in the puzzle's format and must
+//! come to the same answer, or, where they answer different questions, to
+//! answers that agree with each other.
+
+use find::distance::Metric;
+use ims::letters::Letters;
+use num_bigint::BigInt;
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+/// Lists of frequency changes, kept small so that repeats are common.
+fn frequency_changes() -> impl Strategy<Value = Vec<i64>> {
+    prop::collection::vec(-10..=10i64, 1..=20)
+}
+
+fn changes_to_text(changes: &[i64]) -> String {
+    let mut text = String::new();
+    for change in changes {
+        text += &format!("{:+}\n", change);
+    }
+    text
+}
+
+/// Goes round the changes until a frequency repeats, as the looping
+/// calibrator used to, giving up after `max_passes`.
+fn brute_force_first_repeat(changes: &[i64], max_passes: usize) -> Option<(i64, usize, usize)> {
+    let mut seen = HashSet::new();
+    let mut frequency = 0;
+    seen.insert(frequency);
+    for pass in 0..max_passes {
+        for (index, change) in changes.iter().enumerate() {
+            frequency += change;
+            if !seen.insert(frequency) {
+                return Some((frequency, pass, index + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Box IDs from a small alphabet, most of them the same length, so that
+/// many are one letter apart.
+fn box_ids() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec("[abc]{3,4}", 0..=12)
+}
+
+/// Compares every pair of IDs, as find used to, for the pairs that differ
+/// by exactly one letter and where.
+fn brute_force_near_matches(ids: &[String]) -> Vec<(usize, usize, usize)> {
+    let mut matches = Vec::new();
+    for first in 0..ids.len() {
+        for second in (first + 1)..ids.len() {
+            let a: Vec<char> = ids[first].chars().collect();
+            let b: Vec<char> = ids[second].chars().collect();
+            if a.len() != b.len() {
+                continue;
+            }
+            let differences: Vec<usize> = (0..a.len()).filter(|index| a[*index] != b[*index]).collect();
+            if differences.len() == 1 {
+                matches.push((first, second, differences[0]));
+            }
+        }
+    }
+    matches
+}
+
+/// Box IDs of many lengths, so that insertions and deletions turn up.
+fn ragged_box_ids() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec("[ab]{0,6}", 0..=12)
+}
+
+/// Measures every pair of IDs for the ones no further apart than `within`.
+fn brute_force_close_matches(ids: &[String], metric: Metric, within: usize) -> Vec<(usize, usize, usize)> {
+    let ids: Vec<Vec<char>> = ids.iter().map(|id| id.chars().collect()).collect();
+    let mut matches = Vec::new();
+    for first in 0..ids.len() {
+        for second in (first + 1)..ids.len() {
+            let distance = metric.distance(&ids[first], &ids[second]);
+            if distance <= within {
+                matches.push((first, second, distance));
+            }
+        }
+    }
+    matches
+}
+
+/// A claim's id, position and size.
+type Claim = (i32, i32, i32, i32, i32);
+
+/// Claims numbered from 1, packed into a small patch of fabric so that
+/// plenty of them overlap.
+fn claims() -> impl Strategy<Value = Vec<Claim>> {
+    prop::collection::vec((-20..40i32, -20..40i32, 0..=15i32, 0..=15i32), 1..=8)
+        .prop_map(|rectangles| {
+            rectangles.into_iter()
+                .enumerate()
+                .map(|(index, (x, y, width, height))| (index as i32 + 1, x, y, width, height))
+                .collect()
+        })
+}
+
+fn claims_to_text(claims: &[Claim]) -> String {
+    let mut text = String::new();
+    for (id, x, y, width, height) in claims {
+        text += &format!("#{} @ {},{}: {}x{}\n", id, x, y, width, height);
+    }
+    text
+}
+
+/// Claims covering no fabric overlap nothing.
+fn claims_overlap(a: &Claim, b: &Claim) -> bool {
+    let (_, ax, ay, aw, ah) = *a;
+    let (_, bx, by, bw, bh) = *b;
+    aw > 0 && ah > 0 && bw > 0 && bh > 0 && ax < bx + bw && bx < ax + aw && ay < by + bh && by < ay + ah
+}
+
+/// Counts how many claims cover each square inch of the patch the claims
+/// come from, giving the area covered more than once and the ids of the
+/// claims that overlap nothing.
+fn brute_force_overlaps(claims: &[Claim]) -> (i64, Vec<i32>) {
+    let covering = |x, y| claims.iter()
+        .filter(|(_, cx, cy, width, height)| *cx <= x && x < cx + width && *cy <= y && y < cy + height)
+        .map(|claim| claim.0)
+        .collect::<Vec<i32>>();
+
+    let mut area = 0;
+    let mut contested = HashSet::new();
+    for y in -20..55 {
+        for x in -20..55 {
+            let ids = covering(x, y);
+            if ids.len() > 1 {
+                area += 1;
+                contested.extend(ids);
+            }
+        }
+    }
+
+    let alone = claims.iter().map(|claim| claim.0).filter(|id| !contested.contains(id)).collect();
+    (area, alone)
+}
+
+/// Boxes numbered from 1, in three dimensions and packed close enough
+/// together to overlap often.
+fn boxes() -> impl Strategy<Value = Vec<overlaps::boxes::BoxClaim>> {
+    prop::collection::vec((prop::collection::vec(0..10i64, 3), prop::collection::vec(0..=6i64, 3)), 1..=8)
+        .prop_map(|boxes| {
+            boxes.into_iter()
+                .enumerate()
+                .map(|(index, (corner, size))| overlaps::boxes::BoxClaim::new(index as i32 + 1, corner, size).unwrap())
+                .collect()
+        })
+}
+
+/// Counts how many boxes hold each unit cube of the space the boxes come
+/// from, giving the volume held more than once and the indexes of the
+/// boxes that overlap nothing.
+fn brute_force_box_overlaps(boxes: &[overlaps::boxes::BoxClaim]) -> (i128, Vec<usize>) {
+    let mut volume = 0;
+    let mut contested = HashSet::new();
+    for z in 0..16 {
+        for y in 0..16 {
+            for x in 0..16 {
+                let point = [x, y, z];
+                let holding: Vec<usize> = (0..boxes.len())
+                    .filter(|index| (0..3).all(|axis| boxes[*index].corner[axis] <= point[axis] && point[axis] < boxes[*index].far[axis]))
+                    .collect();
+                if holding.len() > 1 {
+                    volume += 1;
+                    contested.extend(holding);
+                }
+            }
+        }
+    }
+
+    let alone = (0..boxes.len()).filter(|index| !contested.contains(index)).collect();
+    (volume, alone)
+}
+
+/// A polymer made from only a few unit types, so that units react often.
+fn polymer() -> impl Strategy<Value = String> {
+    "[aAbBcCdD]{1,200}"
+}
+
+/// A set of plant rules with one rule for every pattern. An empty pattern
+/// never grows a plant, as in the puzzle, or plants would fill every pot.
+fn plant_rules() -> impl Strategy<Value = Vec<bool>> {
+    prop::collection::vec(any::<bool>(), 32).prop_map(|mut results| {
+        results[0] = false;
+        results
+    })
+}
+
+fn initial_state() -> impl Strategy<Value = String> {
+    "[#.]{5,40}"
+}
+
+fn plants_to_text(state: &str, rules: &[bool]) -> String {
+    let mut text = format!("initial state: {}\n\n", state);
+    for (pattern, result) in rules.iter().enumerate() {
+        for bit in (0..5).rev() {
+            text += if pattern & (1 << bit) != 0 { "#" } else { "." };
+        }
+        text += if *result { " => #\n" } else { " => .\n" };
+    }
+    text
+}
+
+/// A grid serial number, negative ones included, as the puzzle doesn't
+/// rule them out.
+fn serial_number() -> impl Strategy<Value = i64> {
+    -10_000..=10_000i64
+}
+
+/// The number of players and the last marble of a game.
+fn game_setup() -> impl Strategy<Value = (usize, usize)> {
+    (1..=30usize, 1..=100usize)
+}
+
+fn game_setup_to_text(players: usize, last_marble: usize) -> String {
+    format!("{} players; last marble is worth {} points", players, last_marble)
+}
+
+proptest! {
+    #[test]
+    fn test_overlaps_agrees_with_brute_force(claims in claims()) {
+        let (area, alone) = brute_force_overlaps(&claims);
+        let parsed = overlaps::claims_from(&claims_to_text(&claims)).unwrap();
+
+        prop_assert_eq!(area, overlaps::process_lines(claims_to_text(&claims)).unwrap());
+        let uncontested: Vec<i32> = overlaps::sweep::uncontested(&parsed).into_iter()
+            .map(|index| parsed[index].claim_id)
+            .collect();
+        prop_assert_eq!(alone, uncontested);
+    }
+
+    /// Adding, removing and resizing claims one at a time keeps a sheet
+    /// agreeing with working everything out from scratch.
+    #[test]
+    fn test_fabric_sheet_agrees_with_sweep(
+        claims in claims(),
+        edits in prop::collection::vec((0..3usize, 1..=8i32, 0..=15i64, 0..=15i64), 0..20),
+    ) {
+        let parsed = overlaps::claims_from(&claims_to_text(&claims)).unwrap();
+        let mut sheet = fabric::sheet::FabricSheet::new();
+        for claim in &parsed {
+            sheet.add(claim.clone()).unwrap();
+        }
+
+        for (edit, claim_id, width, height) in edits {
+            let _ = match edit {
+                0 => sheet.remove(claim_id).map(|_| ()),
+                1 => sheet.resize(claim_id, width, height),
+                _ => sheet.add(overlaps::Claim::new(claim_id, width, height, height, width).unwrap()),
+            };
+
+            let now: Vec<overlaps::Claim> = sheet.claims().cloned().collect();
+            prop_assert_eq!(overlaps::sweep::overlapped_area(&now), Some(sheet.overlapped_area()));
+            let uncontested: Vec<i32> = overlaps::sweep::uncontested(&now).into_iter()
+                .map(|index| now[index].claim_id)
+                .collect();
+            prop_assert_eq!(uncontested, sheet.uncontested());
+        }
+    }
+
+    /// A rearranged layout keeps every claim's size, keeps it on the sheet
+    /// and overlaps nowhere, and only claims it says moved have moved.
+    #[test]
+    fn test_rearranged_claims_dont_overlap(claims in claims(), seed in any::<u64>()) {
+        let parsed = overlaps::claims_from(&claims_to_text(&claims)).unwrap();
+        let layout = match fabric::layout::rearrange(&parsed, 60, 60, seed) {
+            Ok(layout) => layout,
+            Err(error) => return Err(TestCaseError::fail(error.to_string())),
+        };
+
+        prop_assert!(overlaps::sweep::overlapping_pairs(&layout.claims).is_empty());
+        for (claim, placed) in parsed.iter().zip(layout.claims.iter()) {
+            prop_assert_eq!((claim.claim_id, claim.width, claim.height), (placed.claim_id, placed.width, placed.height));
+            prop_assert!(placed.x >= 0 && placed.y >= 0 && placed.right <= 60 && placed.bottom <= 60);
+            prop_assert_eq!(claim != placed, layout.moved.contains(&claim.claim_id));
+        }
+    }
+
+    #[test]
+    fn test_box_overlaps_agree_with_brute_force(boxes in boxes()) {
+        let (volume, alone) = brute_force_box_overlaps(&boxes);
+        prop_assert_eq!(volume, overlaps::boxes::overlapped_volume(&boxes).unwrap());
+        prop_assert_eq!(alone, overlaps::boxes::uncontested(&boxes).unwrap());
+    }
+
+    /// The area only a claim covers is the squares it covers that no
+    /// other claim does.
+    #[test]
+    fn test_exclusive_area_agrees_with_brute_force(claims in claims()) {
+        let parsed = overlaps::claims_from(&claims_to_text(&claims)).unwrap();
+        let graph = overlaps::graph::OverlapGraph::new(&parsed);
+
+        for (index, claim) in parsed.iter().enumerate() {
+            let mut exclusive = 0;
+            for y in claim.y..claim.bottom {
+                for x in claim.x..claim.right {
+                    if parsed.iter().filter(|other| other.contains_point(x, y)).count() == 1 {
+                        exclusive += 1;
+                    }
+                }
+            }
+            prop_assert_eq!(Ok(exclusive), graph.exclusive_area(index));
+        }
+    }
+
+    /// The claim no-overlaps finds is one overlaps doesn't miss: taking it
+    /// away leaves the overlapping area unchanged, and it doesn't overlap
+    /// any other claim.
+    #[test]
+    fn test_no_overlaps_agrees_with_overlaps(claims in claims()) {
+        let text = claims_to_text(&claims);
+        let alone = no_overlaps::process_lines(text.clone()).unwrap();
+
+        if alone == -1 {
+            for claim in &claims {
+                prop_assert!(claims.iter().any(|other| other.0 != claim.0 && claims_overlap(claim, other)));
+            }
+        } else {
+            let claim = claims.iter().find(|claim| claim.0 == alone).unwrap();
+            prop_assert!(claims.iter().all(|other| other.0 == claim.0 || !claims_overlap(claim, other)));
+
+            let others: Vec<Claim> = claims.iter().filter(|other| other.0 != alone).cloned().collect();
+            prop_assert_eq!(
+                overlaps::process_lines(text).unwrap(),
+                overlaps::process_lines(claims_to_text(&others)).unwrap());
+        }
+    }
+}
+
+proptest! {
+    /// Working the first repeat out from the first pass finds the same one
+    /// as going round until it's found.
+    #[test]
+    fn test_first_repeat_agrees_with_brute_force(changes in frequency_changes()) {
+        let parsed = calibrator::read_changes::<i64>(&changes_to_text(&changes)).unwrap();
+        let found = looping_calibrator::find_first_repeat(&parsed).unwrap();
+
+        let max_passes = 1_000;
+        match brute_force_first_repeat(&changes, max_passes) {
+            Some((frequency, passes, line)) => {
+                let found = found.unwrap();
+                prop_assert_eq!(frequency, found.frequency);
+                prop_assert_eq!(BigInt::from(passes), found.passes);
+                prop_assert_eq!(line, found.line);
+            },
+            None => {
+                if let Some(found) = found {
+                    prop_assert!(found.passes >= BigInt::from(max_passes));
+                }
+            },
+        }
+    }
+
+    /// Filing IDs under their hashes finds the same pairs as comparing every
+    /// pair.
+    #[test]
+    fn test_near_matches_agrees_with_brute_force(ids in box_ids()) {
+        let borrowed: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
+        let found: Vec<(usize, usize, usize)> = find::near_matches(&borrowed, &Letters::default()).iter()
+            .map(|near_match| (near_match.first, near_match.second, near_match.position))
+            .collect();
+        prop_assert_eq!(brute_force_near_matches(&ids), found);
+    }
+
+    /// Looking IDs up by their pieces finds the same pairs as measuring
+    /// every pair, whichever way the distance is measured.
+    #[test]
+    fn test_close_matches_agrees_with_brute_force(ids in ragged_box_ids(), within in 0..=3usize) {
+        let borrowed: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
+        for metric in [Metric::Hamming, Metric::Levenshtein].iter() {
+            let found: Vec<(usize, usize, usize)> = find::close_matches(&borrowed, *metric, within, &Letters::default()).iter()
+                .map(|close_match| (close_match.first, close_match.second, close_match.distance))
+                .collect();
+            prop_assert_eq!(brute_force_close_matches(&ids, *metric, within), found);
+        }
+    }
+
+    /// Removing the problem unit gives the shortest of the polymers reducer
+    /// gets from removing each unit in turn.
+    #[test]
+    fn test_good_better_bestest_agrees_with_reducer(polymer in polymer()) {
+        let mut shortest = polymer.len();
+        for unit in "abcd".chars() {
+            let filtered: String = polymer.chars().filter(|c| c.to_ascii_lowercase() != unit).collect();
+            if filtered.len() == polymer.len() {
+                continue;
+            }
+            let length = if filtered.is_empty() { 0 } else { reducer::process(&filtered).unwrap() };
+            shortest = shortest.min(length);
+        }
+
+        prop_assert_eq!(shortest, good_better_bestest::process(&polymer).unwrap());
+        prop_assert!(reducer::process(&polymer).unwrap() <= polymer.len());
+    }
+
+    /// marbles-100 plays the same game as marbles, a hundred times longer.
+    #[test]
+    fn test_marbles_100_agrees_with_marbles((players, last_marble) in game_setup()) {
+        prop_assert_eq!(
+            marbles::process(&game_setup_to_text(players, last_marble * 100)).unwrap(),
+            marbles_100::process(&game_setup_to_text(players, last_marble)).unwrap());
+    }
+}
+
+proptest! {
+    // Every variant searches the whole 300 by 300 grid
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn test_chronal_charge_variants_agree_on_3x3(serial in serial_number()) {
+        let (x, y, power) = chronal_charge::process(serial);
+        prop_assert_eq!((x, y, power), chronal_charge_deluxe::process_square(serial, 3));
+        prop_assert_eq!((x, y, power), chronal_charge_deluxe_royale::process_square(serial, 3));
+    }
+
+    #[test]
+    fn test_chronal_charge_deluxe_agrees_with_royale(serial in serial_number(), side in 1..=30i64) {
+        prop_assert_eq!(
+            chronal_charge_deluxe::process_square(serial, side),
+            chronal_charge_deluxe_royale::process_square(serial, side));
+    }
+}
+
+proptest! {
+    // The betterer plays up to 100 generations before projecting, so this
+    // checks both playing and projecting
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_underground_gol_betterer_agrees_with_underground_gol(
+        state in initial_state(),
+        rules in plant_rules(),
+        generations in 0..=250usize,
+    ) {
+        let text = plants_to_text(&state, &rules);
+        prop_assert_eq!(
+            underground_gol::sum_after(&text, generations).unwrap(),
+            underground_gol_betterer::sum_after(&text, generations as i64).unwrap());
+    }
+}
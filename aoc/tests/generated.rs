@@ -0,0 +1,65 @@
+//! Every solution should solve the inputs `aoc-gen` makes for its day.
+
+use aoc::solutions;
+use aoc_common::{Answer, Solution};
+
+/// Sizes small enough to solve quickly without optimisations. Marbles-100
+/// plays a hundred times as many marbles as asked for.
+fn test_size(day: u32) -> Option<usize> {
+    match day {
+        5 => Some(5_000),
+        9 => Some(500),
+        _ => None,
+    }
+}
+
+fn all_solutions() -> Vec<Box<dyn Solution>> {
+    let mut all = solutions::solutions();
+    all.extend(solutions::variants());
+
+    // Trying every square size by brute force takes too long
+    all.retain(|solution| solution.name() != "chronal-charge-deluxe");
+    all
+}
+
+#[test]
+fn test_solutions_solve_generated_inputs() {
+    for seed in 0..3 {
+        for solution in all_solutions() {
+            let day = solution.day();
+            let input = aoc_gen::generate(day, seed, test_size(day)).unwrap();
+            let result = solution.solve(&input);
+            assert!(result.is_ok(), "{} failed on seed {}: {}", solution.name(), seed, result.unwrap_err());
+        }
+    }
+}
+
+#[test]
+fn test_solutions_solve_small_generated_inputs() {
+    for size in [1, 2, 10].iter() {
+        for solution in all_solutions() {
+            let input = aoc_gen::generate(solution.day(), 1, Some(*size)).unwrap();
+            let result = solution.solve(&input);
+            assert!(result.is_ok(), "{} failed on size {}: {}", solution.name(), size, result.unwrap_err());
+        }
+    }
+}
+
+#[test]
+fn test_generated_claims_have_one_alone() {
+    let solution = solutions::find(3, 2, None).unwrap();
+    for seed in 0..3 {
+        let input = aoc_gen::generate(3, seed, test_size(3)).unwrap();
+        assert_ne!(Answer::from(-1), solution.solve(&input).unwrap());
+    }
+}
+
+#[test]
+fn test_generated_box_ids_have_a_near_match() {
+    let solution = solutions::find(2, 2, None).unwrap();
+    let input = aoc_gen::generate(2, 1, None).unwrap();
+    match solution.solve(&input).unwrap() {
+        Answer::Text(common) => assert_eq!(25, common.len()),
+        other => panic!("Expected the common letters, got {}", other),
+    }
+}